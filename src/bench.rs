@@ -1,7 +1,17 @@
-use crate::config::{PingThingsArgs, RpcType};
+use crate::config::{PingThingsArgs, RoutingPolicy, RpcType, SenderRoute};
+use crate::core::blockhash::BlockhashCache;
+use crate::core::leaders::{LeaderTracker, LeaderWindow};
 use crate::meteora::types::MeteoraSwapParams;
 use crate::tx_senders::{
+    compute_budget::{self, ComputeBudgetTable},
+    confirmation::{ConfirmationTracker, SentTx},
+    connection::build_client,
     create_tx_sender,
+    error::SendError,
+    guard::{GuardError, GuardedSender},
+    nonce::NonceManager,
+    priority_fee::PriorityFeeOracle,
+    scoring::SenderScores,
     solana_rpc::TxMetrics,
     transaction::{build_meteora_swap_tx, TransactionConfig},
     TxSender,
@@ -16,10 +26,46 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_client::rpc_config::RpcSimulateTransactionConfig;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::hash::Hash;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
 use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// How often resends without a confirmation tracker check for blockhash expiry.
+const EXPIRY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Snipes whose WSOL spend is not settled yet; WSOL maintenance waits while any is open.
+#[derive(Clone, Default)]
+pub struct SnipesInFlight(Arc<AtomicUsize>);
+
+impl SnipesInFlight {
+    /// Counts a snipe until the returned guard is dropped.
+    pub fn enter(&self) -> SnipeInFlight {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        SnipeInFlight(self.0.clone())
+    }
+
+    // Only read by WSOL maintenance, which the tool binaries do not include.
+    #[allow(dead_code)]
+    pub fn any(&self) -> bool {
+        self.0.load(Ordering::Relaxed) > 0
+    }
+}
+
+pub struct SnipeInFlight(Arc<AtomicUsize>);
+
+impl Drop for SnipeInFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
 
 /// Holds shared state for broadcasting (or simulating) swap transactions.
 #[derive(Clone)]
@@ -28,34 +74,136 @@ pub struct Bench {
     pub config: PingThingsArgs,
     /// Pre-built static tx parameters (keypair, cu-limit, etc.).
     pub tx_config: TransactionConfig,
-    /// Channel for optional external metrics (not used here but kept for compatibility).
-    #[allow(dead_code)]
+    /// Landing metrics, one record per sender and snipe.
     pub tx_subscribe_sender: mpsc::Sender<TxMetrics>,
     /// List of RPC / Jito senders.
     pub rpcs: Vec<Arc<dyn TxSender>>,
     /// Shared Reqwest client.
     #[allow(dead_code)]
     pub client: Client,
+    /// Dynamic compute-unit price estimation (`None` keeps the static price).
+    pub fee_oracle: Option<Arc<PriorityFeeOracle>>,
+    /// Per pool-variant compute budgets measured by `calibrate_compute_budget`.
+    pub compute_budgets: Arc<ComputeBudgetTable>,
+    /// Durable nonce for per-sender variants (`None` sends one shared blockhash tx).
+    pub nonce: Option<Arc<NonceManager>>,
+    /// Latest confirmed blockhash shared by all transaction builders.
+    pub blockhash: BlockhashCache,
+    /// Follows broadcast transactions until they land or expire (`None` when disabled).
+    pub tracker: Option<Arc<ConfirmationTracker>>,
+    /// Historical per-sender performance used by the routing policy.
+    pub scores: Arc<SenderScores>,
+    /// Upcoming leaders, deciding whether bundles are worth sending (`None` when disabled).
+    pub leaders: Option<Arc<LeaderTracker>>,
+    /// Snipes not yet landed or expired.
+    pub snipes_in_flight: SnipesInFlight,
 }
 
 impl Bench {
     /// Create a new `Bench` from global `PingThingsArgs`.
     pub fn new(config: PingThingsArgs) -> Self {
-        let (tx_subscribe_sender, _rx) = mpsc::channel(100);
-
-        // Build once – can be reused for every tx
-        let tx_config: TransactionConfig = config.clone().into();
-        let client = Client::new();
-
         // Convert every entry in `rpc:` map into a concrete sender
         let rpcs = config
             .rpc
             .clone()
             .into_iter()
             .filter_map(|(name, rpc)| {
-                create_tx_sender(name, rpc, tx_config.clone(), client.clone())
+                let guard = config.sender_guard.for_sender(&rpc);
+                // Each sender gets its own pool, tuned by its connection overrides.
+                let client = build_client(&config.connections.for_sender(&rpc));
+                create_tx_sender(name, rpc, client)
+                    .map(|sender| Arc::new(GuardedSender::new(sender, guard)) as Arc<dyn TxSender>)
             })
             .collect::<Vec<_>>();
+        Self::with_senders(config, rpcs)
+    }
+
+    /// Bench broadcasting to `rpcs` instead of senders built from `config.rpc`.
+    pub fn with_senders(config: PingThingsArgs, rpcs: Vec<Arc<dyn TxSender>>) -> Self {
+        let scores = Arc::new(SenderScores::load(config.routing.stats_file.clone()));
+        scores.spawn_persistence();
+
+        let (tx_subscribe_sender, mut metrics_rx) = mpsc::channel::<TxMetrics>(100);
+        let metrics_scores = scores.clone();
+        tokio::spawn(async move {
+            while let Some(metrics) = metrics_rx.recv().await {
+                metrics_scores.record_outcome(&metrics);
+                match serde_json::to_string(&metrics) {
+                    Ok(line) => info!(target: "metrics", "{}", line),
+                    Err(e) => warn!("cannot serialize tx metrics: {}", e),
+                }
+            }
+        });
+
+        // Build once – can be reused for every tx
+        let tx_config: TransactionConfig = config.clone().into();
+        let client = build_client(&config.connections);
+
+        let fee_oracle = config.priority_fee.enabled.then(|| {
+            Arc::new(PriorityFeeOracle::new(
+                config.priority_fee.clone(),
+                config.http_rpc.clone(),
+                config.compute_unit_price,
+            ))
+        });
+
+        let blockhash = BlockhashCache::new(config.blockhash.clone());
+
+        let nonce = if config.nonce.enabled {
+            match Pubkey::from_str(&config.nonce.account) {
+                Ok(account) => Some(Arc::new(NonceManager::new(
+                    account,
+                    config.http_rpc.clone(),
+                    blockhash.clone(),
+                ))),
+                Err(e) => {
+                    warn!("invalid nonce account '{}': {}", config.nonce.account, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let leaders = config.leaders.enabled.then(|| {
+            let tracker = Arc::new(LeaderTracker::new(config.leaders.clone()));
+            tracker.spawn(config.http_rpc.clone(), config.ws_rpc.clone());
+            tracker
+        });
+
+        if config.routing.policy == RoutingPolicy::WeightedTips && nonce.is_none() {
+            warn!("routing.policy weighted_tips has no effect without a durable nonce");
+        }
+        if !config.nonce.enabled && config.routing.policy != RoutingPolicy::All {
+            warn!(
+                "without a durable nonce only bundle outcomes are scored; routing relies on them"
+            );
+        }
+
+        if config.resend.enabled && !config.confirmation.enabled {
+            warn!("resend without confirmation tracking stops only at expiry or budget exhaustion");
+        }
+
+        for (name, rpc) in &config.rpc {
+            let has_overrides =
+                rpc.tip.is_some() || rpc.compute_unit_price.is_some() || rpc.tip_account.is_some();
+            if has_overrides && nonce.is_none() {
+                warn!("per-sender overrides of '{name}' ignored – they require a durable nonce");
+            }
+            if let Some(tip_account) = &rpc.tip_account {
+                if Pubkey::from_str(tip_account).is_err() {
+                    warn!("invalid tip_account '{tip_account}' for sender '{name}' ignored");
+                }
+            }
+        }
+
+        let tracker = config.confirmation.enabled.then(|| {
+            Arc::new(ConfirmationTracker::new(
+                config.confirmation.clone(),
+                config.http_rpc.clone(),
+                tx_subscribe_sender.clone(),
+            ))
+        });
 
         Self {
             config,
@@ -63,26 +211,101 @@ impl Bench {
             tx_subscribe_sender,
             rpcs,
             client,
+            fee_oracle,
+            compute_budgets: Arc::new(ComputeBudgetTable::default()),
+            nonce,
+            blockhash,
+            tracker,
+            scores,
+            leaders,
+            snipes_in_flight: SnipesInFlight::default(),
+        }
+    }
+
+    /// Simulate the swap template for each configured pool variant and store the budgets.
+    pub async fn calibrate_compute_budget(&self) {
+        compute_budget::calibrate(
+            &self.config,
+            &self.tx_config,
+            &self.compute_budgets,
+            &self.blockhash,
+        )
+        .await;
+    }
+
+    /// Feed a transaction seen on the stream to the priority-fee oracle.
+    pub fn observe_transaction(&self, transaction: &VersionedTransaction) {
+        if let Some(oracle) = &self.fee_oracle {
+            oracle.observe(transaction);
+        }
+    }
+
+    /// Per-snipe copy of `tx_config` with the calibrated budget for the pool variant
+    /// and the compute-unit price re-estimated.
+    fn snipe_tx_config(&self, params: &MeteoraSwapParams) -> TransactionConfig {
+        let mut tx_config = self.tx_config.clone();
+        if let Some(budget) = self.compute_budgets.get(params.variant) {
+            tx_config.compute_unit_limit = budget.compute_unit_limit;
+            tx_config.loaded_accounts_data_size_limit = budget.loaded_accounts_data_size;
+        }
+        if let Some(oracle) = &self.fee_oracle {
+            tx_config.compute_unit_price = oracle.estimate(&params.writable_pool_accounts());
+            info!(
+                "Priority fee for pool {}: {} micro-lamports/CU",
+                params.pool, tx_config.compute_unit_price
+            );
+        }
+        tx_config
+    }
+
+    /// Per-sender variant of the snipe config (tip, price, tip recipient from `rpc:`).
+    /// Overrides only apply to nonce transactions, where variants are mutually exclusive.
+    fn sender_tx_config(&self, base: &TransactionConfig, sender_name: &str) -> TransactionConfig {
+        let mut tx_config = base.clone();
+        if tx_config.nonce_account.is_none() {
+            return tx_config;
+        }
+        let Some(rpc) = self.config.rpc.get(sender_name) else {
+            return tx_config;
+        };
+
+        if let Some(tip) = rpc.tip {
+            tx_config.tip = (tip * LAMPORTS_PER_SOL as f64) as u64;
+        } else if self.config.routing.policy == RoutingPolicy::WeightedTips {
+            tx_config.tip = (tx_config.tip as f64 * self.tip_factor(sender_name)) as u64;
+        }
+        if let Some(price) = rpc.compute_unit_price {
+            tx_config.compute_unit_price = price;
         }
+        if let Some(tip_account) = rpc.tip_account.as_deref() {
+            tx_config.tip_account = Pubkey::from_str(tip_account).ok();
+        }
+        tx_config
     }
 
     /// Either **simulate** or **broadcast** a single swap using a given sender.
+    /// Returns the broadcast transaction for confirmation tracking.
     async fn send_or_simulate(
         &self,
+        index: u32,
         rpc_sender: Arc<dyn TxSender>,
+        snipe_config: TransactionConfig,
         recent_blockhash: Hash,
         params: MeteoraSwapParams,
-    ) -> Result<()> {
+    ) -> Result<Option<SentTx>> {
+        let tx_config = self.sender_tx_config(&snipe_config, &rpc_sender.name());
+
         // -------- Simulation mode --------
         if self.config.simulate {
             let rpc_client = RpcClient::new(self.config.http_rpc.clone());
-            let latest_blockhash = rpc_client
-                .get_latest_blockhash()
+            let latest_blockhash = self
+                .blockhash
+                .get_or_fetch(&rpc_client)
                 .await
                 .context("failed to fetch recent blockhash for simulation")?;
 
             let versioned_tx = build_meteora_swap_tx(
-                &self.tx_config,
+                &tx_config,
                 &RpcType::SolanaRpc, // RPC type for simulation purposes
                 latest_blockhash,
                 &params,
@@ -141,18 +364,29 @@ impl Bench {
                 debug!("[SIM_ACCOUNT_{}] {}", idx, pk);
             }
 
-            return Ok(());
+            return Ok(None);
         }
 
         // -------- Real broadcast --------
-        // Capture current block height before submitting the transaction so we can
-        // later compute how many blocks it took to land (≈ latency in blocks).
-        let slot_sent = rpc_sender.get_block_height().await.ok();
+        let tx = build_meteora_swap_tx(
+            &tx_config,
+            &rpc_sender.rpc_type(),
+            recent_blockhash,
+            &params,
+        );
 
         let started = Instant::now();
-        let tx_result = rpc_sender
-            .send_meteora_swap(&params, recent_blockhash)
-            .await?;
+        let tx_result = rpc_sender.send_transaction(&tx).await;
+        // Sends refused by the guard never reached the provider.
+        let refused = matches!(&tx_result, Err(e) if e.is::<GuardError>());
+        if !refused {
+            self.scores.record_send(
+                &rpc_sender.name(),
+                tx_result.is_ok(),
+                started.elapsed().as_millis() as u64,
+            );
+        }
+        let tx_result = tx_result?;
         info!(
             "Swap via {} took {} ms – {:?}",
             rpc_sender.name(),
@@ -160,53 +394,322 @@ impl Bench {
             tx_result
         );
 
-        // Fetch latest block height after broadcast and compute Δ in slots, if possible.
-        match rpc_sender.get_block_height().await {
-            Ok(height) => {
-                if let Some(sent) = slot_sent {
-                    let delta = height.saturating_sub(sent);
-                    info!(
-                        "{} confirmed near block {}, Δ={} slots",
-                        rpc_sender.name(),
-                        height,
-                        delta
-                    );
-                } else {
-                    info!("{} confirmed near block {}", rpc_sender.name(), height);
+        Ok(tx.signatures.first().map(|signature| SentTx {
+            sender: rpc_sender.clone(),
+            index,
+            signature: *signature,
+            tx: tx.clone(),
+            result: tx_result,
+            sent_at: started,
+            settled: CancellationToken::new(),
+        }))
+    }
+
+    /// Senders for the next snipe under the routing policy, with their index in `rpcs`.
+    fn route(&self) -> Vec<(usize, Arc<dyn TxSender>)> {
+        let route_of = |sender: &Arc<dyn TxSender>| {
+            self.config
+                .rpc
+                .get(&sender.name())
+                .map(|rpc| rpc.route)
+                .unwrap_or_default()
+        };
+        let mut selected: Vec<(usize, Arc<dyn TxSender>)> = Vec::new();
+        let mut candidates = Vec::new();
+        for (index, sender) in self.rpcs.iter().enumerate() {
+            match route_of(sender) {
+                SenderRoute::Always => selected.push((index, sender.clone())),
+                SenderRoute::Never => {}
+                SenderRoute::Auto => candidates.push((index, sender.clone())),
+            }
+        }
+
+        if self.config.routing.policy == RoutingPolicy::TopK {
+            let mut scored: Vec<(f64, usize, Arc<dyn TxSender>)> = candidates
+                .into_iter()
+                .map(|(index, sender)| (self.scores.score(&sender.name()), index, sender))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            scored.truncate(self.config.routing.top_k);
+            debug!(
+                "top-k senders: {:?}",
+                scored
+                    .iter()
+                    .map(|(score, _, sender)| format!("{}={:.3}", sender.name(), score))
+                    .collect::<Vec<_>>()
+            );
+            candidates = scored
+                .into_iter()
+                .map(|(_, index, sender)| (index, sender))
+                .collect();
+        }
+        selected.extend(candidates);
+        self.route_by_leaders(selected)
+    }
+
+    /// Drops the bundle senders when none of the upcoming leaders runs the Jito client, so
+    /// the snipe goes out through TPU and the plain relays only. `always` senders are kept,
+    /// and so is everything when the schedule is unknown or nothing else is left.
+    fn route_by_leaders(
+        &self,
+        selected: Vec<(usize, Arc<dyn TxSender>)>,
+    ) -> Vec<(usize, Arc<dyn TxSender>)> {
+        let Some(leaders) = &self.leaders else {
+            return selected;
+        };
+        let window = leaders.window();
+        debug!("upcoming leaders: {:?}", window);
+        if window != LeaderWindow::NonJito {
+            return selected;
+        }
+        let without_bundles: Vec<_> = selected
+            .iter()
+            .filter(|(_, sender)| {
+                !matches!(sender.rpc_type(), RpcType::Jito | RpcType::JitoGrpc)
+                    || self
+                        .config
+                        .rpc
+                        .get(&sender.name())
+                        .is_some_and(|rpc| rpc.route == SenderRoute::Always)
+            })
+            .cloned()
+            .collect();
+        if without_bundles.is_empty() {
+            debug!("no Jito leader ahead but only bundle senders left, sending anyway");
+            return selected;
+        }
+        without_bundles
+    }
+
+    /// Tip multiplier under `weighted_tips`: `min_tip_factor` for a score of zero up to
+    /// 1.0 for the best-scoring sender.
+    fn tip_factor(&self, sender_name: &str) -> f64 {
+        let best = self
+            .rpcs
+            .iter()
+            .map(|sender| self.scores.score(&sender.name()))
+            .fold(0.0, f64::max);
+        if best <= 0.0 {
+            return 1.0;
+        }
+        let min = self.config.routing.min_tip_factor.clamp(0.0, 1.0);
+        min + (1.0 - min) * self.scores.score(sender_name) / best
+    }
+
+    /// Rebroadcasts each signed transaction through its sender every `resend.interval_ms`
+    /// until `done` is cancelled (status arrived or blockhash expired), the transaction
+    /// reached a final state or the sender's resend budget is spent.
+    /// `last_valid_block_height` is `None` for nonce transactions.
+    fn spawn_resends(
+        &self,
+        sent: &[SentTx],
+        last_valid_block_height: Option<u64>,
+        done: &CancellationToken,
+    ) {
+        let resend = &self.config.resend;
+        let interval = Duration::from_millis(resend.interval_ms.max(50));
+
+        // The confirmation tracker ends `done` at expiry; without it, watch the height here.
+        if let (None, Some(last_valid)) = (&self.tracker, last_valid_block_height) {
+            let rpc = RpcClient::new(self.config.http_rpc.clone());
+            let done = done.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = done.cancelled() => return,
+                        _ = tokio::time::sleep(EXPIRY_POLL_INTERVAL) => {}
+                    }
+                    match rpc.get_block_height().await {
+                        Ok(height) if height > last_valid => {
+                            debug!("blockhash expired at height {}, resends stopped", height);
+                            done.cancel();
+                            return;
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!("block height poll failed: {e}"),
+                    }
                 }
+            });
+        }
+
+        for tx in sent {
+            let name = tx.sender.name();
+            if !resend.senders.is_empty() && !resend.senders.contains(&name) {
+                continue;
             }
-            Err(e) => {
-                warn!(
-                    "failed to fetch block-height via {}: {}",
-                    rpc_sender.name(),
-                    e
-                );
+            let budget = self
+                .config
+                .rpc
+                .get(&name)
+                .and_then(|rpc| rpc.max_resends)
+                .unwrap_or(resend.max_resends);
+            if budget == 0 {
+                continue;
             }
-        }
 
-        Ok(())
+            let sender = tx.sender.clone();
+            let signed = tx.tx.clone();
+            let done = done.clone();
+            let settled = tx.settled.clone();
+            tokio::spawn(async move {
+                for attempt in 1..=budget {
+                    tokio::select! {
+                        _ = done.cancelled() => {
+                            debug!("{} stopped resending after {} attempts", name, attempt - 1);
+                            return;
+                        }
+                        _ = settled.cancelled() => {
+                            debug!("{} transaction settled after {} resends", name, attempt - 1);
+                            return;
+                        }
+                        _ = tokio::time::sleep(interval) => {}
+                    }
+                    if let Err(e) = sender.resend_transaction(&signed).await {
+                        debug!("resend {}/{} via {} failed: {:#}", attempt, budget, name, e);
+                        match e.downcast_ref::<SendError>() {
+                            Some(send_err) if !send_err.is_retryable() => {
+                                debug!("{} stopped resending: {}", name, send_err.kind());
+                                return;
+                            }
+                            // Give a throttling provider one extra interval to recover.
+                            Some(SendError::RateLimited { .. }) => {
+                                tokio::select! {
+                                    _ = done.cancelled() => return,
+                                    _ = settled.cancelled() => return,
+                                    _ = tokio::time::sleep(interval) => {}
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                debug!("{} resend budget of {} exhausted", name, budget);
+            });
+        }
     }
 
     /// Public helper the controller calls after it builds `MeteoraSwapParams`.
-    pub async fn send_buy_tx_meteora(&self, recent_blockhash: Hash, params: MeteoraSwapParams) {
+    /// `last_valid_block_height` belongs to `recent_blockhash` (`None` if unknown).
+    pub async fn send_buy_tx_meteora(
+        &self,
+        recent_blockhash: Hash,
+        last_valid_block_height: Option<u64>,
+        params: MeteoraSwapParams,
+    ) {
+        let in_flight = self.snipes_in_flight.enter();
+        let mut tx_config = self.snipe_tx_config(&params);
+        let mut recent_blockhash = recent_blockhash;
+        let mut nonce_lease = None;
+        if let Some(nonce) = &self.nonce {
+            match nonce.checkout().await {
+                Ok(lease) => {
+                    tx_config.nonce_account = Some(nonce.account());
+                    recent_blockhash = lease.hash();
+                    nonce_lease = Some(lease);
+                }
+                Err(e) => warn!(
+                    "durable nonce unavailable, falling back to blockhash: {:#}",
+                    e
+                ),
+            }
+        }
+        // Requested before the first send, so landing latency counts from broadcast start.
+        let slot_sent = self
+            .tracker
+            .as_ref()
+            .map(|tracker| tracker.spawn_slot_query());
         let mut tasks = Vec::new();
 
-        for rpc in &self.rpcs {
-            let sender = rpc.clone();
+        for (index, sender) in self.route() {
+            let cfg = tx_config.clone();
             let rb = recent_blockhash;
             let p = params.clone();
             let bench_ref = self.clone();
 
             let handle = tokio::spawn(async move {
-                if let Err(e) = bench_ref.send_or_simulate(sender, rb, p).await {
-                    error!("swap send failed: {:?}", e);
+                match bench_ref
+                    .send_or_simulate(index as u32, sender, cfg, rb, p)
+                    .await
+                {
+                    Ok(sent) => sent,
+                    Err(e) if e.is::<GuardError>() => {
+                        debug!("swap send skipped: {}", e);
+                        None
+                    }
+                    Err(e) => {
+                        match e.downcast_ref::<SendError>() {
+                            Some(send_err) if send_err.is_alert() => {
+                                error!("ALERT swap send failed: {}", send_err);
+                                info!(
+                                    target: "metrics",
+                                    "{}",
+                                    serde_json::json!({
+                                        "sender": send_err.provider(),
+                                        "alert": send_err.kind(),
+                                    })
+                                );
+                            }
+                            Some(send_err) => warn!("swap send failed: {}", send_err),
+                            None => error!("swap send failed: {:?}", e),
+                        }
+                        None
+                    }
                 }
             });
             tasks.push(handle);
         }
 
+        let mut sent = Vec::new();
         for h in tasks {
-            let _ = h.await;
+            if let Ok(Some(tx)) = h.await {
+                sent.push(tx);
+            }
+        }
+
+        // Only one nonce variant can land; find out which relay delivered it.
+        if let (Some(nonce), Some(lease)) = (&self.nonce, nonce_lease) {
+            if !sent.is_empty() {
+                let variants = sent
+                    .iter()
+                    .map(|tx| (tx.sender.name(), tx.signature))
+                    .collect();
+                let nonce = nonce.clone();
+                let authority = self.tx_config.keypair.clone();
+                let expiry = Duration::from_secs(self.config.nonce.expiry_secs);
+                tokio::spawn(async move {
+                    match nonce
+                        .await_landed_variant(lease, variants, &authority, expiry)
+                        .await
+                    {
+                        Some((name, sig)) => info!("Nonce variant via {} landed: {}", name, sig),
+                        None => warn!("No nonce variant landed within {:?}", expiry),
+                    }
+                });
+            }
+        }
+
+        // Nonce transactions have no blockhash expiry; the tracker's timeout applies.
+        let last_valid_block_height = match tx_config.nonce_account {
+            Some(_) => None,
+            None => last_valid_block_height,
+        };
+        let done = CancellationToken::new();
+        if self.config.resend.enabled {
+            self.spawn_resends(&sent, last_valid_block_height, &done);
+        }
+
+        // The WSOL balance settles once the snipe lands, or at the latest at the timeout.
+        let settled = done.clone();
+        let settle_timeout = Duration::from_secs(self.config.confirmation.timeout_secs);
+        tokio::spawn(async move {
+            let _ = tokio::time::timeout(settle_timeout, settled.cancelled()).await;
+            drop(in_flight);
+        });
+
+        if let (Some(tracker), Some(slot_sent)) = (&self.tracker, slot_sent) {
+            let slot_sent = slot_sent.await.unwrap_or(0);
+            let exclusive = tx_config.nonce_account.is_some();
+            tracker.track(sent, slot_sent, last_valid_block_height, exclusive, done);
         }
 
         if self.config.simulate {
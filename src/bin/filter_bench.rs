@@ -0,0 +1,159 @@
+/// Measures the geyser fast-path filter against the full conversion of a transaction; prints
+/// timings only and asserts nothing
+///
+/// Usage: cargo run --release --bin filter_bench -- [ITERATIONS]
+
+// Re-import project modules via explicit paths
+#[allow(dead_code)]
+#[path = "../bench.rs"]
+mod bench;
+#[allow(dead_code)]
+#[path = "../config/mod.rs"]
+mod config;
+#[allow(dead_code)]
+#[path = "../core/mod.rs"]
+mod core;
+#[allow(dead_code)]
+#[path = "../geyser/mod.rs"]
+mod geyser;
+#[allow(dead_code)]
+#[path = "../meteora/mod.rs"]
+mod meteora;
+#[allow(dead_code)]
+#[path = "../tx_senders/mod.rs"]
+mod tx_senders;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use meteora::constants::{INIT_POOL_DISCRIM, METEORA_PROGRAM, SWAP_DISCRIM};
+use meteora::controller::{find_init_pool_instruction, raw_invokes_init_pool};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use yellowstone_grpc_proto::convert_from::{create_tx_meta, create_tx_versioned};
+use yellowstone_grpc_proto::solana::storage::confirmed_block::{
+    CompiledInstruction, Message as RawMessage, MessageHeader, Transaction as RawTransaction,
+    TransactionStatusMeta as RawMeta,
+};
+
+const DEFAULT_ITERATIONS: u32 = 100_000;
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS);
+
+    // Most Meteora traffic is swaps; those are what the fast path rejects.
+    let swap = raw_transaction(&meteora_transaction(&SWAP_DISCRIM, 15));
+    let init = raw_transaction(&meteora_transaction(&INIT_POOL_DISCRIM, 24));
+    let meta = RawMeta::default();
+
+    println!("{} iterations per case\n", iterations);
+    for (name, raw) in [("swap", &swap), ("init pool", &init)] {
+        let full = time(iterations, || full_path(raw, &meta));
+        let fast = time(iterations, || raw_invokes_init_pool(raw));
+        // Both paths consume their input in the bot; here it has to be cloned first.
+        let clone = time(iterations, || {
+            black_box(raw.clone());
+            black_box(meta.clone());
+            true
+        });
+        let full_ns = per_iteration(full.saturating_sub(clone), iterations);
+        let fast_ns = per_iteration(fast, iterations);
+        println!("{}:", name);
+        println!("  full decode + instruction scan: {:>10.1} ns", full_ns);
+        println!("  fast path on raw protobuf:      {:>10.1} ns", fast_ns);
+        println!(
+            "  saved per transaction:          {:>10.1} ns",
+            full_ns - fast_ns
+        );
+    }
+}
+
+/// What every transaction went through before the fast path.
+fn full_path(raw: &RawTransaction, meta: &RawMeta) -> bool {
+    let Ok(transaction) = create_tx_versioned(raw.clone()) else {
+        return false;
+    };
+    let Ok(meta) = create_tx_meta(meta.clone()) else {
+        return false;
+    };
+    let Ok(instructions) = crate::core::extract_instructions(&meta, &transaction) else {
+        return false;
+    };
+    find_init_pool_instruction(&instructions).is_some()
+}
+
+fn time(iterations: u32, mut f: impl FnMut() -> bool) -> Duration {
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    started.elapsed()
+}
+
+fn per_iteration(total: Duration, iterations: u32) -> f64 {
+    total.as_nanos() as f64 / iterations.max(1) as f64
+}
+
+fn meteora_transaction(discriminator: &[u8; 8], accounts: usize) -> VersionedTransaction {
+    let payer = Pubkey::new_unique();
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&[0u8; 16]);
+    let instruction = Instruction {
+        program_id: METEORA_PROGRAM,
+        accounts: (0..accounts)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect(),
+        data,
+    };
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer), &Hash::new_unique());
+    VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+/// The protobuf form Yellowstone delivers.
+fn raw_transaction(transaction: &VersionedTransaction) -> RawTransaction {
+    let VersionedMessage::Legacy(message) = &transaction.message else {
+        unreachable!("only legacy messages are built here");
+    };
+    RawTransaction {
+        signatures: transaction
+            .signatures
+            .iter()
+            .map(|signature| signature.as_ref().to_vec())
+            .collect(),
+        message: Some(RawMessage {
+            header: Some(MessageHeader {
+                num_required_signatures: message.header.num_required_signatures as u32,
+                num_readonly_signed_accounts: message.header.num_readonly_signed_accounts as u32,
+                num_readonly_unsigned_accounts: message.header.num_readonly_unsigned_accounts
+                    as u32,
+            }),
+            account_keys: message
+                .account_keys
+                .iter()
+                .map(|key| key.to_bytes().to_vec())
+                .collect(),
+            recent_blockhash: message.recent_blockhash.to_bytes().to_vec(),
+            instructions: message
+                .instructions
+                .iter()
+                .map(|inst| CompiledInstruction {
+                    program_id_index: inst.program_id_index as u32,
+                    accounts: inst.accounts.clone(),
+                    data: inst.data.clone(),
+                })
+                .collect(),
+            versioned: false,
+            address_table_lookups: vec![],
+        }),
+    }
+}
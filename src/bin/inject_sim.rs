@@ -9,6 +9,8 @@ mod bench;
 mod config;
 #[path = "../core/mod.rs"]
 mod core;
+// Only the handler types are needed here, not the stream client.
+#[allow(dead_code)]
 #[path = "../geyser/mod.rs"]
 mod geyser;
 #[path = "../meteora/mod.rs"]
@@ -19,20 +21,11 @@ mod tx_senders;
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
-use base64::{engine::general_purpose, Engine as _};
 use bench::Bench;
-use bincode::config::standard as bincode_standard_config;
 use config::PingThingsArgs;
 use meteora::controller::MeteoraController;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::message::v0::LoadedAddresses;
-use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
-use solana_sdk::transaction::VersionedTransaction;
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
-use solana_transaction_status::{
-    EncodedTransaction, TransactionStatusMeta, UiLoadedAddresses, UiTransactionStatusMeta,
-};
 use tracing_subscriber;
 
 #[tokio::main]
@@ -50,70 +43,19 @@ async fn main() -> Result<()> {
 
     // Fetch transaction from RPC
     let rpc = RpcClient::new("https://api.mainnet-beta.solana.com".to_string());
-    let tx: EncodedConfirmedTransactionWithStatusMeta = rpc
-        .get_transaction(&signature, UiTransactionEncoding::Base64)
-        .await
-        .context("RPC get_transaction failed")?;
-
-    // Decode transaction
-    let encoded_tx = match &tx.transaction.transaction {
-        EncodedTransaction::Binary(bin, _) => bin,
-        _ => anyhow::bail!("transaction encoding is not binary"),
-    };
-    let tx_bytes = general_purpose::STANDARD
-        .decode(encoded_tx)
-        .context("base64 decode failed")?;
-    let (versioned_tx, _): (VersionedTransaction, usize) =
-        bincode::serde::decode_from_slice(&tx_bytes, bincode_standard_config())
-            .context("bincode deserialize failed")?;
-
-    let ui_meta: UiTransactionStatusMeta = tx
-        .transaction
-        .meta
-        .context("missing meta in RPC response")?;
-
-    // Construct TransactionStatusMeta from UI meta
-    let meta = TransactionStatusMeta {
-        status: ui_meta.err.map_or(Ok(()), Err),
-        fee: ui_meta.fee,
-        pre_balances: ui_meta.pre_balances,
-        post_balances: ui_meta.post_balances,
-        inner_instructions: None,
-        log_messages: ui_meta.log_messages.into(),
-        pre_token_balances: None,
-        post_token_balances: None,
-        rewards: ui_meta.rewards.into(),
-        loaded_addresses: {
-            let opt_ui_loaded_addresses: Option<UiLoadedAddresses> =
-                ui_meta.loaded_addresses.into();
-            match opt_ui_loaded_addresses {
-                Some(ui_loaded) => LoadedAddresses {
-                    writable: ui_loaded
-                        .writable
-                        .into_iter()
-                        .map(|s| Pubkey::from_str(&s))
-                        .collect::<Result<Vec<_>, _>>()?,
-                    readonly: ui_loaded
-                        .readonly
-                        .into_iter()
-                        .map(|s| Pubkey::from_str(&s))
-                        .collect::<Result<Vec<_>, _>>()?,
-                },
-                None => LoadedAddresses::default(),
-            }
-        },
-        return_data: None,
-        compute_units_consumed: ui_meta.compute_units_consumed.into(),
-    };
+    let (versioned_tx, meta) = crate::core::fetch_transaction(&rpc, &signature).await?;
 
     // Run through Meteora controller
     let mut config = PingThingsArgs::new();
     config.simulate = true; // Override: inject_sim ALWAYS simulates
     let bench = Bench::new(config.clone());
+    if config.compute_budget.auto_calibrate {
+        bench.calibrate_compute_budget().await;
+    }
     let mut controller = MeteoraController::new(config, bench);
 
     controller
-        .transaction_handler(signature, versioned_tx, meta, false, 0)
+        .transaction_handler(signature, &versioned_tx, Some(&meta), false, 0)
         .await?;
 
     Ok(())
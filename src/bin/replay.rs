@@ -0,0 +1,127 @@
+/// Replays captured Geyser updates through the controller, with every sender replaced by
+/// a stub that records the transactions instead of sending them
+///
+/// Usage: cargo run --release --bin replay -- <CAPTURE_FILE>... [--speed FACTOR]
+
+// Re-import project modules via explicit paths
+#[allow(dead_code)]
+#[path = "../bench.rs"]
+mod bench;
+#[allow(dead_code)]
+#[path = "../config/mod.rs"]
+mod config;
+#[allow(dead_code)]
+#[path = "../core/mod.rs"]
+mod core;
+#[allow(dead_code)]
+#[path = "../geyser/mod.rs"]
+mod geyser;
+#[allow(dead_code)]
+#[path = "../meteora/mod.rs"]
+mod meteora;
+#[allow(dead_code)]
+#[path = "../tx_senders/mod.rs"]
+mod tx_senders;
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64_STD, Engine as _};
+use bench::Bench;
+use bincode::serde as bincode_serde;
+use config::{BlockhashPolicy, PingThingsArgs, RoutingPolicy};
+use geyser::dispatch::Dispatcher;
+use meteora::controller::MeteoraController;
+use tracing::info;
+use tx_senders::recording::RecordingSender;
+use tx_senders::TxSender;
+
+/// Original pace.
+const DEFAULT_SPEED: f64 = 1.0;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let (paths, speed) = parse_args()?;
+
+    let mut config = PingThingsArgs::new();
+    // Nothing may reach the network, and the same capture must build the same transactions.
+    config.simulate = false;
+    config.priority_fee.enabled = false;
+    config.compute_budget.auto_calibrate = false;
+    config.nonce.enabled = false;
+    config.blockhash.policy = BlockhashPolicy::PoolTx;
+    config.confirmation.enabled = false;
+    config.resend.enabled = false;
+    config.leaders.enabled = false;
+    // The live bot's sender statistics must neither steer nor absorb the replay.
+    config.routing.stats_file = String::new();
+    config.routing.policy = RoutingPolicy::All;
+
+    // The configured senders are never built, so none of them connects or authenticates.
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let senders = config
+        .rpc
+        .iter()
+        .map(|(name, rpc)| {
+            Arc::new(RecordingSender::new(
+                name.clone(),
+                rpc.rpc_type.clone(),
+                sent.clone(),
+            )) as Arc<dyn TxSender>
+        })
+        .collect();
+    let bench = Bench::with_senders(config.clone(), senders);
+
+    let controller = MeteoraController::new(config.clone(), bench);
+    let dispatch = Dispatcher::new()
+        .with_handler(controller)
+        .spawn(&config.dispatch);
+
+    let replayed = geyser::replay::replay(&paths, speed, &config.geyser, &dispatch).await?;
+    dispatch.drained().await;
+
+    let sent = sent.lock().unwrap();
+    info!(
+        "Replayed {} updates, {} transactions recorded",
+        replayed,
+        sent.len()
+    );
+    // One JSON line per recorded send, for diffing two runs.
+    for recorded in sent.iter() {
+        let tx_bytes =
+            bincode_serde::encode_to_vec(&recorded.transaction, bincode::config::legacy())
+                .context("cannot serialize tx")?;
+        let line = serde_json::json!({
+            "sender": recorded.sender,
+            "signature": recorded.transaction.signatures.first().map(|s| s.to_string()),
+            "transaction": BASE64_STD.encode(tx_bytes),
+        });
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+fn parse_args() -> Result<(Vec<PathBuf>, f64)> {
+    let mut paths = Vec::new();
+    let mut speed = DEFAULT_SPEED;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--speed" {
+            let value = args.next().context("--speed needs a value")?;
+            speed = value.parse().context("invalid --speed")?;
+            if speed.is_nan() || speed < 0.0 {
+                bail!("--speed must not be negative");
+            }
+        } else {
+            paths.push(PathBuf::from(arg));
+        }
+    }
+    if paths.is_empty() {
+        bail!("usage: replay <CAPTURE_FILE>... [--speed FACTOR]");
+    }
+    Ok((paths, speed))
+}
@@ -1,3 +1,4 @@
+use crate::meteora::types::PoolVariant;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt;
@@ -20,6 +21,38 @@ pub struct PingThingsArgs {
     pub min_amount_out: f64,
     #[serde(default)]
     pub simulate: bool,
+    #[serde(default)]
+    pub priority_fee: PriorityFeeConfig,
+    #[serde(default)]
+    pub compute_budget: ComputeBudgetConfig,
+    #[serde(default)]
+    pub wsol: WsolConfig,
+    #[serde(default)]
+    pub nonce: NonceConfig,
+    #[serde(default)]
+    pub blockhash: BlockhashConfig,
+    #[serde(default)]
+    pub confirmation: ConfirmationConfig,
+    #[serde(default)]
+    pub resend: ResendConfig,
+    #[serde(default)]
+    pub routing: RoutingConfig,
+    #[serde(default)]
+    pub sender_guard: SenderGuardConfig,
+    #[serde(default)]
+    pub connections: ConnectionConfig,
+    #[serde(default)]
+    pub leaders: LeaderConfig,
+    #[serde(default)]
+    pub geyser: GeyserConfig,
+    #[serde(default)]
+    pub dispatch: DispatchConfig,
+    #[serde(default)]
+    pub logs_fallback: LogsFallbackConfig,
+    #[serde(default)]
+    pub entries: EntriesConfig,
+    #[serde(default)]
+    pub capture: CaptureConfig,
 }
 
 // Custom Debug implementation that redacts private key
@@ -38,6 +71,22 @@ impl fmt::Debug for PingThingsArgs {
             .field("buy_amount", &self.buy_amount)
             .field("min_amount_out", &self.min_amount_out)
             .field("simulate", &self.simulate)
+            .field("priority_fee", &self.priority_fee)
+            .field("compute_budget", &self.compute_budget)
+            .field("wsol", &self.wsol)
+            .field("nonce", &self.nonce)
+            .field("blockhash", &self.blockhash)
+            .field("confirmation", &self.confirmation)
+            .field("resend", &self.resend)
+            .field("routing", &self.routing)
+            .field("sender_guard", &self.sender_guard)
+            .field("connections", &self.connections)
+            .field("leaders", &self.leaders)
+            .field("geyser", &self.geyser)
+            .field("dispatch", &self.dispatch)
+            .field("logs_fallback", &self.logs_fallback)
+            .field("entries", &self.entries)
+            .field("capture", &self.capture)
             .finish()
     }
 }
@@ -48,10 +97,17 @@ pub enum RpcType {
     #[default]
     SolanaRpc,
     Jito,
+    /// Jito block-engine gRPC searcher API (bundle results streamed back)
+    #[serde(rename = "jito_grpc")]
+    JitoGrpc,
     /// bloXroute Trader API
     Bloxroute,
     /// NextBlock transaction API
     NextBlock,
+    /// Direct QUIC to the leaders' TPU ports; `url` is the RPC used for the leader schedule.
+    Tpu,
+    /// Any HTTP relay, described by the `relay` request template.
+    Relay,
 }
 #[derive(Clone, Debug, Deserialize)]
 pub struct RpcConfig {
@@ -60,11 +116,633 @@ pub struct RpcConfig {
     pub auth: Option<String>,
     #[serde(default)]
     pub rpc_type: RpcType,
+    /// Per-sender tip in SOL; only applied to nonce variants.
+    #[serde(default)]
+    pub tip: Option<f64>,
+    /// Per-sender compute unit price; only applied to nonce variants.
+    #[serde(default)]
+    pub compute_unit_price: Option<u64>,
+    /// Per-sender tip recipient; also enables the tip for non-Jito senders.
+    #[serde(default)]
+    pub tip_account: Option<String>,
+    /// Per-sender rebroadcast budget; overrides `resend.max_resends`.
+    #[serde(default)]
+    pub max_resends: Option<u32>,
+    /// TPU only: websocket endpoint for slot updates.
+    #[serde(default)]
+    pub ws_url: Option<String>,
+    /// TPU only: upcoming slots whose leaders receive the transaction.
+    #[serde(default)]
+    pub fanout_slots: Option<u64>,
+    /// Keypair file: staked identity for TPU QoS, or the searcher auth keypair for `jito_grpc`.
+    #[serde(default)]
+    pub identity_keypair: Option<String>,
+    /// Relay only: how the submit request is built and the response read.
+    #[serde(default)]
+    pub relay: Option<RelayConfig>,
+    /// Manual override of the routing policy for this sender.
+    #[serde(default)]
+    pub route: SenderRoute,
+    /// Overrides `sender_guard.rate_limit_per_sec` for this sender.
+    #[serde(default)]
+    pub rate_limit_per_sec: Option<f64>,
+    /// Overrides `sender_guard.burst` for this sender.
+    #[serde(default)]
+    pub burst: Option<u32>,
+    /// Overrides `connections.ping_interval_secs` for this sender.
+    #[serde(default)]
+    pub ping_interval_secs: Option<u64>,
+    /// Overrides `connections.http2_keep_alive_secs` for this sender.
+    #[serde(default)]
+    pub http2_keep_alive_secs: Option<u64>,
+    /// Overrides `connections.pool_max_idle_per_host` for this sender.
+    #[serde(default)]
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+/// Per-sender routing override.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SenderRoute {
+    /// Selected by the routing policy.
+    #[default]
+    Auto,
+    /// Always used, not counted against `top_k`.
+    Always,
+    /// Never used.
+    Never,
+}
+
+/// How the request body of a relay sender is produced.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelayEncoding {
+    /// `body` template with placeholders substituted, sent as `application/json`.
+    #[default]
+    Json,
+    /// `body` template with placeholders substituted, sent as `text/plain`.
+    Text,
+    /// Serialized transaction bytes as `application/octet-stream`; `body` is ignored.
+    Binary,
+}
+
+/// Request template of a generic HTTP relay. `{tx_base64}`, `{tx_base58}` and `{auth}`
+/// are substituted in `body` and header values.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RelayConfig {
+    pub method: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub encoding: RelayEncoding,
+    /// Dot-separated path to the signature in the JSON response (e.g. `result` or
+    /// `data.signature`); unset means the signed transaction's own signature is used.
+    pub signature_path: Option<String>,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self {
+            method: "POST".to_string(),
+            headers: HashMap::new(),
+            body: String::new(),
+            encoding: RelayEncoding::Json,
+            signature_path: None,
+        }
+    }
+}
+
+/// Dynamic compute-unit price estimation; when disabled `compute_unit_price` is used as is.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct PriorityFeeConfig {
+    pub enabled: bool,
+    /// Percentile (0-100) of the sampled fees to pay.
+    pub percentile: u8,
+    /// Lower cap in micro-lamports per CU.
+    pub min_price: u64,
+    /// Upper cap in micro-lamports per CU.
+    pub max_price: u64,
+    /// Also sample compute-unit prices of competing Meteora transactions from geyser.
+    pub observe_geyser: bool,
+    /// Number of geyser observations kept.
+    pub observation_window: usize,
+    /// Interval between background `getRecentPrioritizationFees` polls.
+    pub rpc_poll_interval_ms: u64,
+    /// Budget for one `getRecentPrioritizationFees` poll.
+    pub rpc_timeout_ms: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            percentile: 75,
+            min_price: 0,
+            max_price: 50_000_000,
+            observe_geyser: false,
+            observation_window: 200,
+            rpc_poll_interval_ms: 2000,
+            rpc_timeout_ms: 1000,
+        }
+    }
+}
+
+/// Compute-unit limit calibration and loaded-accounts data size limit.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ComputeBudgetConfig {
+    /// Simulate the swap template per pool variant at startup and size the budget from it.
+    pub auto_calibrate: bool,
+    /// Headroom added to measured values (0.2 = +20%).
+    pub safety_margin: f64,
+    /// `SetLoadedAccountsDataSizeLimit` in bytes used until calibrated; 0 omits the instruction.
+    pub loaded_accounts_data_size_limit: u32,
+    /// Init-pool transaction signature used as the swap template for each pool variant.
+    pub calibration_txs: HashMap<PoolVariant, String>,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            auto_calibrate: false,
+            safety_margin: 0.2,
+            loaded_accounts_data_size_limit: 0,
+            calibration_txs: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WsolMode {
+    /// Wrap `buy_amount` inside every snipe and close the WSOL account afterwards.
+    #[default]
+    Ephemeral,
+    /// Keep a pre-funded WSOL account topped up by a background task.
+    Persistent,
+}
+
+/// WSOL handling; balances are in SOL.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct WsolConfig {
+    pub mode: WsolMode,
+    /// Balance the maintenance task wraps up to.
+    pub target_balance: f64,
+    /// Top up once the balance drops below this.
+    pub min_balance: f64,
+    /// Unwrap down to `target_balance` once the balance exceeds this; 0 disables.
+    pub max_balance: f64,
+    pub check_interval_secs: u64,
+}
+
+impl WsolConfig {
+    /// Persistent balances must cover a buy, or snipes would run short of WSOL.
+    pub fn validate(&self, buy_amount: f64) -> anyhow::Result<()> {
+        if self.mode != WsolMode::Persistent {
+            return Ok(());
+        }
+        if self.min_balance < buy_amount || self.target_balance < buy_amount {
+            anyhow::bail!(
+                "wsol.min_balance and wsol.target_balance must be at least buy_amount ({})",
+                buy_amount
+            );
+        }
+        if self.max_balance > 0.0 && self.max_balance < self.target_balance {
+            anyhow::bail!("wsol.max_balance must be 0 or at least wsol.target_balance");
+        }
+        Ok(())
+    }
+}
+
+impl Default for WsolConfig {
+    fn default() -> Self {
+        Self {
+            mode: WsolMode::Ephemeral,
+            target_balance: 0.01,
+            min_balance: 0.005,
+            max_balance: 0.0,
+            check_interval_secs: 5,
+        }
+    }
+}
+
+/// Durable nonce used to sign mutually exclusive per-sender transaction variants.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NonceConfig {
+    pub enabled: bool,
+    /// Nonce account; its authority must be the bot keypair.
+    pub account: String,
+    /// Advance the nonce if no variant lands within this window, so none can land later.
+    pub expiry_secs: u64,
+}
+
+impl Default for NonceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            account: String::new(),
+            expiry_secs: 60,
+        }
+    }
+}
+
+/// Which source the blockhash of a snipe comes from.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockhashPolicy {
+    /// Reuse the pool-init transaction's blockhash.
+    PoolTx,
+    /// `getLatestBlockhash` polling only.
+    Rpc,
+    /// Geyser block-meta of confirmed slots only.
+    Geyser,
+    /// Whichever of RPC and geyser reported the higher slot.
+    #[default]
+    Newest,
+}
+
+impl BlockhashPolicy {
+    /// `pool_tx` never reads the cache, so it needs no poller.
+    pub fn uses_rpc(self) -> bool {
+        matches!(self, BlockhashPolicy::Rpc | BlockhashPolicy::Newest)
+    }
+
+    pub fn uses_geyser(self) -> bool {
+        matches!(self, BlockhashPolicy::Geyser | BlockhashPolicy::Newest)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BlockhashConfig {
+    pub policy: BlockhashPolicy,
+    pub rpc_poll_interval_ms: u64,
+    /// Cached values older than this are not used for snipes.
+    pub max_age_ms: u64,
+}
+
+impl Default for BlockhashConfig {
+    fn default() -> Self {
+        Self {
+            policy: BlockhashPolicy::Newest,
+            rpc_poll_interval_ms: 400,
+            max_age_ms: 30_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ConfirmationConfig {
+    pub enabled: bool,
+    pub poll_interval_ms: u64,
+    /// Give up on transactions without a known expiry (durable nonce) after this long.
+    pub timeout_secs: u64,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_ms: 400,
+            timeout_secs: 90,
+        }
+    }
+}
+
+/// Rebroadcasting of the signed snipe until it lands or its blockhash expires.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ResendConfig {
+    pub enabled: bool,
+    pub interval_ms: u64,
+    /// Default rebroadcasts per sender and snipe.
+    pub max_resends: u32,
+    /// Senders (names from `rpc`) that rebroadcast; empty means all.
+    pub senders: Vec<String>,
+}
+
+impl Default for ResendConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 400,
+            max_resends: 20,
+            senders: Vec::new(),
+        }
+    }
+}
+
+/// How senders are picked for a snipe.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingPolicy {
+    /// Every sender, as before.
+    #[default]
+    All,
+    /// The `top_k` senders by historical score.
+    TopK,
+    /// Every sender, with per-sender tips scaled by score (durable nonce only).
+    WeightedTips,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RoutingConfig {
+    pub policy: RoutingPolicy,
+    pub top_k: usize,
+    /// Tip fraction paid through the worst-scoring sender under `weighted_tips`.
+    pub min_tip_factor: f64,
+    /// Where sender statistics are kept between runs; empty disables persistence.
+    pub stats_file: String,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            policy: RoutingPolicy::All,
+            top_k: 3,
+            min_tip_factor: 0.5,
+            stats_file: "sender_stats.json".to_string(),
+        }
+    }
+}
+
+/// Rate limit and circuit breaker applied around every sender.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SenderGuardConfig {
+    /// Sends per second; 0 disables the limit.
+    pub rate_limit_per_sec: f64,
+    pub burst: u32,
+    /// Consecutive failures that open the circuit; 0 disables the breaker.
+    pub failure_threshold: u32,
+    /// How long an open circuit refuses sends before a probe is let through.
+    pub open_secs: u64,
+}
+
+impl Default for SenderGuardConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_per_sec: 0.0,
+            burst: 5,
+            failure_threshold: 5,
+            open_secs: 30,
+        }
+    }
+}
+
+impl SenderGuardConfig {
+    /// Applies the per-sender overrides of an `rpc` entry.
+    pub fn for_sender(&self, rpc: &RpcConfig) -> Self {
+        Self {
+            rate_limit_per_sec: rpc.rate_limit_per_sec.unwrap_or(self.rate_limit_per_sec),
+            burst: rpc.burst.unwrap_or(self.burst),
+            ..self.clone()
+        }
+    }
+}
+
+/// HTTP client tuning and connection warm-up of the senders.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// Connect every sender before the bot goes live.
+    pub warmup: bool,
+    /// Longest wait for the warm-up; senders not ready by then are reported and used anyway.
+    pub ready_timeout_secs: u64,
+    /// Interval of keep-alive requests to each sender endpoint; 0 disables them.
+    pub ping_interval_secs: u64,
+    pub connect_timeout_ms: u64,
+    /// Idle connections are closed after this long.
+    pub pool_idle_timeout_secs: u64,
+    pub pool_max_idle_per_host: usize,
+    /// HTTP/2 PING interval on open connections; 0 disables it.
+    pub http2_keep_alive_secs: u64,
+    pub http2_keep_alive_timeout_secs: u64,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            warmup: true,
+            ready_timeout_secs: 10,
+            ping_interval_secs: 30,
+            connect_timeout_ms: 3000,
+            pool_idle_timeout_secs: 300,
+            pool_max_idle_per_host: 4,
+            http2_keep_alive_secs: 15,
+            http2_keep_alive_timeout_secs: 5,
+        }
+    }
+}
+
+impl ConnectionConfig {
+    /// Applies the per-sender overrides of an `rpc` entry.
+    pub fn for_sender(&self, rpc: &RpcConfig) -> Self {
+        Self {
+            ping_interval_secs: rpc.ping_interval_secs.unwrap_or(self.ping_interval_secs),
+            http2_keep_alive_secs: rpc
+                .http2_keep_alive_secs
+                .unwrap_or(self.http2_keep_alive_secs),
+            pool_max_idle_per_host: rpc
+                .pool_max_idle_per_host
+                .unwrap_or(self.pool_max_idle_per_host),
+            ..self.clone()
+        }
+    }
+}
+
+/// Leader-schedule-aware choice between bundle senders and the other paths.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LeaderConfig {
+    pub enabled: bool,
+    /// Slots, starting with the current one, whose leaders decide the route of a snipe.
+    pub lookahead_slots: u64,
+    /// Identity pubkeys of validators running the Jito client.
+    pub jito_validators: Vec<String>,
+}
+
+impl Default for LeaderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookahead_slots: 4,
+            jito_validators: Vec::new(),
+        }
+    }
+}
+
+/// Geyser endpoints and reconnection of their streams.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct GeyserConfig {
+    /// Endpoints subscribed concurrently; empty uses `geyser_url` / `geyser_x_token`.
+    pub endpoints: Vec<GeyserEndpoint>,
+    /// Interval of the per-endpoint arrival statistics.
+    pub stats_interval_secs: u64,
+    /// First reconnect delay; doubled on every failed attempt.
+    pub reconnect_initial_ms: u64,
+    pub reconnect_max_ms: u64,
+    /// Random spread of each delay, as a fraction of it.
+    pub reconnect_jitter: f64,
+    /// Re-subscribe with `from_slot` set to the last processed slot.
+    pub resume_from_slot: bool,
+    /// Interval of client pings on every stream; 0 disables them (server pings are still
+    /// answered).
+    pub ping_interval_secs: u64,
+    /// A stream whose slot does not advance for this long is reconnected; 0 disables it.
+    pub stall_timeout_secs: u64,
+    /// Recent transaction signatures remembered to drop duplicates from other endpoints
+    /// and replays.
+    pub dedup_capacity: usize,
+}
+
+impl Default for GeyserConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            stats_interval_secs: 60,
+            reconnect_initial_ms: 250,
+            reconnect_max_ms: 30_000,
+            reconnect_jitter: 0.2,
+            resume_from_slot: true,
+            ping_interval_secs: 10,
+            stall_timeout_secs: 30,
+            dedup_capacity: 50_000,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct GeyserEndpoint {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub x_token: Option<String>,
+}
+
+impl fmt::Debug for GeyserEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeyserEndpoint")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("x_token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Per-handler work queues between the Geyser stream and the handlers.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DispatchConfig {
+    /// Queued transactions per handler before the oldest non-priority one is dropped.
+    pub queue_capacity: usize,
+    /// Interval of the queue-depth metrics; 0 disables them.
+    pub metrics_interval_secs: u64,
+}
+
+impl Default for DispatchConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 1024,
+            metrics_interval_secs: 30,
+        }
+    }
+}
+
+/// `logsSubscribe` event source on `ws_rpc`, used while Geyser is down or not configured.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LogsFallbackConfig {
+    /// Run the source next to Geyser, taking over while no stream is live. Without a
+    /// Geyser endpoint it always runs.
+    pub enabled: bool,
+    /// `getTransaction` attempts per init-pool log match until it is confirmed.
+    pub fetch_attempts: u32,
+    pub fetch_retry_ms: u64,
+}
+
+impl Default for LogsFallbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fetch_attempts: 20,
+            fetch_retry_ms: 250,
+        }
+    }
+}
+
+/// Entry stream of a ShredStream proxy, for pool initializations before execution.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct EntriesConfig {
+    pub enabled: bool,
+    /// gRPC endpoint of the proxy.
+    pub url: String,
+}
+
+impl Default for EntriesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "http://127.0.0.1:9999".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureMode {
+    /// Every update received from every endpoint.
+    All,
+    /// Only transactions that pass the handlers' raw prefilters.
+    #[default]
+    Transactions,
+}
+
+/// Recording of the Geyser stream to length-prefixed files, for debugging and replay.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CaptureConfig {
+    pub enabled: bool,
+    pub mode: CaptureMode,
+    /// Directory the capture files are written to.
+    pub dir: String,
+    /// A new file is started once the current one reaches this size...
+    pub max_file_mb: u64,
+    /// ...or is this old; 0 rotates by size only.
+    pub rotate_secs: u64,
+    /// Updates waiting for the writer before new ones are dropped.
+    pub queue_capacity: usize,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: CaptureMode::Transactions,
+            dir: "captures".to_string(),
+            max_file_mb: 256,
+            rotate_secs: 3600,
+            queue_capacity: 10_000,
+        }
+    }
 }
 
 impl PingThingsArgs {
     pub fn new() -> Self {
         let config_yaml = fs::read_to_string("./config.yaml").expect("cannot find config file");
-        serde_yaml::from_str::<PingThingsArgs>(&config_yaml).expect("invalid config file")
+        let config =
+            serde_yaml::from_str::<PingThingsArgs>(&config_yaml).expect("invalid config file");
+        config
+            .wsol
+            .validate(config.buy_amount)
+            .expect("invalid wsol config");
+        config
     }
 }
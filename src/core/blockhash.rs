@@ -0,0 +1,180 @@
+use crate::config::{BlockhashConfig, BlockhashPolicy};
+
+use serde_json::json;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcBlockhash};
+use solana_sdk::hash::Hash;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Blocks a blockhash stays usable for after the block that produced it.
+const MAX_PROCESSING_AGE: u64 = 150;
+/// Geyser block-meta entries kept while waiting for their slot to be confirmed.
+const MAX_PENDING_BLOCKS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockhashSource {
+    Rpc,
+    Geyser,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CachedBlockhash {
+    pub hash: Hash,
+    pub last_valid_block_height: u64,
+    pub slot: u64,
+    pub source: BlockhashSource,
+    pub updated_at: Instant,
+}
+
+/// Latest confirmed blockhash, kept fresh by RPC polling and/or geyser block-meta updates.
+#[derive(Clone)]
+pub struct BlockhashCache {
+    config: BlockhashConfig,
+    latest: Arc<RwLock<Option<CachedBlockhash>>>,
+    /// Geyser block-meta by slot: (blockhash, block height), published once confirmed.
+    pending: Arc<Mutex<BTreeMap<u64, (Hash, u64)>>>,
+}
+
+impl BlockhashCache {
+    pub fn new(config: BlockhashConfig) -> Self {
+        Self {
+            config,
+            latest: Arc::new(RwLock::new(None)),
+            pending: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn policy(&self) -> BlockhashPolicy {
+        self.config.policy
+    }
+
+    /// Latest cached value, if it is fresh enough to sign with.
+    pub fn latest(&self) -> Option<CachedBlockhash> {
+        let max_age = Duration::from_millis(self.config.max_age_ms);
+        self.latest
+            .read()
+            .unwrap()
+            .filter(|cached| cached.updated_at.elapsed() <= max_age)
+    }
+
+    /// Blockhash a snipe is signed with and its last valid block height, read together
+    /// before the cache can rotate. Falls back to the pool-init transaction's own
+    /// blockhash, whose expiry is unknown.
+    pub fn resolve(&self, pool_tx_blockhash: Hash) -> (Hash, Option<u64>) {
+        if self.config.policy == BlockhashPolicy::PoolTx {
+            return (pool_tx_blockhash, None);
+        }
+        match self.latest() {
+            Some(cached) => (cached.hash, Some(cached.last_valid_block_height)),
+            None => {
+                warn!("no fresh cached blockhash, using the pool-init transaction's");
+                (pool_tx_blockhash, None)
+            }
+        }
+    }
+
+    /// Cached blockhash, or a fresh one from `rpc` when the cache is empty or stale.
+    pub async fn get_or_fetch(&self, rpc: &RpcClient) -> anyhow::Result<Hash> {
+        if let Some(cached) = self.latest() {
+            return Ok(cached.hash);
+        }
+        Ok(rpc.get_latest_blockhash().await?)
+    }
+
+    /// Records a geyser block-meta update; it is published when its slot is confirmed.
+    pub fn on_block_meta(&self, slot: u64, blockhash: &str, block_height: Option<u64>) {
+        let (Ok(hash), Some(block_height)) = (Hash::from_str(blockhash), block_height) else {
+            return;
+        };
+        let mut pending = self.pending.lock().unwrap();
+        pending.insert(slot, (hash, block_height));
+        while pending.len() > MAX_PENDING_BLOCKS {
+            pending.pop_first();
+        }
+    }
+
+    /// Publishes the pending block-meta of a slot that reached `confirmed`.
+    pub fn on_slot_confirmed(&self, slot: u64) {
+        let entry = {
+            let mut pending = self.pending.lock().unwrap();
+            let entry = pending.remove(&slot);
+            // Older slots can no longer become the newest confirmed block.
+            pending.retain(|pending_slot, _| *pending_slot > slot);
+            entry
+        };
+        if let Some((hash, block_height)) = entry {
+            self.publish(CachedBlockhash {
+                hash,
+                last_valid_block_height: block_height + MAX_PROCESSING_AGE,
+                slot,
+                source: BlockhashSource::Geyser,
+                updated_at: Instant::now(),
+            });
+        }
+    }
+
+    /// Polls `getLatestBlockhash` (confirmed) every `rpc_poll_interval_ms`.
+    pub fn spawn_rpc_poller(&self, http_rpc: String) {
+        let cache = self.clone();
+        let interval = Duration::from_millis(self.config.rpc_poll_interval_ms.max(50));
+        tokio::spawn(async move {
+            let rpc = RpcClient::new(http_rpc);
+            loop {
+                match rpc
+                    .send::<Response<RpcBlockhash>>(
+                        RpcRequest::GetLatestBlockhash,
+                        json!([{ "commitment": "confirmed" }]),
+                    )
+                    .await
+                {
+                    Ok(response) => match Hash::from_str(&response.value.blockhash) {
+                        Ok(hash) => cache.publish(CachedBlockhash {
+                            hash,
+                            last_valid_block_height: response.value.last_valid_block_height,
+                            slot: response.context.slot,
+                            source: BlockhashSource::Rpc,
+                            updated_at: Instant::now(),
+                        }),
+                        Err(e) => warn!("invalid blockhash from RPC: {e}"),
+                    },
+                    Err(e) => warn!("blockhash poll failed: {e}"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Stores `candidate` if its source is allowed by the policy and it is not older
+    /// than the current value.
+    fn publish(&self, candidate: CachedBlockhash) {
+        let allowed = match self.config.policy {
+            BlockhashPolicy::Rpc => candidate.source == BlockhashSource::Rpc,
+            BlockhashPolicy::Geyser => candidate.source == BlockhashSource::Geyser,
+            BlockhashPolicy::Newest | BlockhashPolicy::PoolTx => true,
+        };
+        if !allowed {
+            return;
+        }
+
+        let max_age = Duration::from_millis(self.config.max_age_ms);
+        let mut latest = self.latest.write().unwrap();
+        let replace = match latest.as_ref() {
+            Some(current) => {
+                candidate.slot >= current.slot || current.updated_at.elapsed() > max_age
+            }
+            None => true,
+        };
+        if replace {
+            debug!(
+                "blockhash {} (slot {}, {:?})",
+                candidate.hash, candidate.slot, candidate.source
+            );
+            *latest = Some(candidate);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::config::LeaderConfig;
+
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Slot leaders fetched per schedule request.
+const SCHEDULE_WINDOW: u64 = 1024;
+/// The schedule is refetched once fewer than this many slots ahead are known.
+const SCHEDULE_MARGIN: u64 = 256;
+/// How often the schedule coverage (and, without slot updates, the slot) is checked.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Slot updates older than this are considered stalled and the slot is polled over RPC.
+const SLOT_STALE_AFTER: Duration = Duration::from_secs(2);
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Who produces the next blocks, as far as routing is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderWindow {
+    /// At least one of the upcoming leaders runs the Jito client.
+    Jito,
+    /// None of the upcoming leaders can land a bundle.
+    NonJito,
+    /// Slot or schedule not known yet.
+    Unknown,
+}
+
+#[derive(Default)]
+struct Schedule {
+    first_slot: u64,
+    leaders: Vec<Pubkey>,
+}
+
+impl Schedule {
+    fn leader(&self, slot: u64) -> Option<Pubkey> {
+        let offset = slot.checked_sub(self.first_slot)?;
+        self.leaders.get(offset as usize).copied()
+    }
+
+    fn covers_until(&self) -> u64 {
+        self.first_slot + self.leaders.len() as u64
+    }
+}
+
+/// Follows the current slot (websocket slot subscription, RPC polling as fallback) and the
+/// leader schedule, and tells whether the next leaders accept Jito bundles.
+pub struct LeaderTracker {
+    config: LeaderConfig,
+    jito_validators: HashSet<Pubkey>,
+    slot: AtomicU64,
+    slot_updated_at: RwLock<Option<Instant>>,
+    schedule: RwLock<Schedule>,
+}
+
+impl LeaderTracker {
+    pub fn new(config: LeaderConfig) -> Self {
+        let jito_validators = config
+            .jito_validators
+            .iter()
+            .filter_map(|key| match Pubkey::from_str(key) {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    warn!("invalid Jito validator '{}' ignored: {}", key, e);
+                    None
+                }
+            })
+            .collect::<HashSet<_>>();
+        if jito_validators.is_empty() {
+            warn!("leader routing enabled without jito_validators; bundles will be skipped");
+        }
+        Self {
+            config,
+            jito_validators,
+            slot: AtomicU64::new(0),
+            slot_updated_at: RwLock::new(None),
+            schedule: RwLock::new(Schedule::default()),
+        }
+    }
+
+    /// Starts the slot subscription and the schedule refresh.
+    pub fn spawn(self: &Arc<Self>, http_rpc: String, ws_rpc: String) {
+        tokio::spawn(self.clone().slot_loop(ws_rpc));
+        tokio::spawn(self.clone().schedule_loop(http_rpc));
+    }
+
+    pub fn current_slot(&self) -> Option<u64> {
+        match self.slot.load(Ordering::Relaxed) {
+            0 => None,
+            slot => Some(slot),
+        }
+    }
+
+    /// Leaders of the current and the next `lookahead_slots - 1` slots, if all are known.
+    pub fn upcoming_leaders(&self) -> Option<Vec<Pubkey>> {
+        let slot = self.current_slot()?;
+        let schedule = self.schedule.read().unwrap();
+        (slot..slot + self.config.lookahead_slots.max(1))
+            .map(|slot| schedule.leader(slot))
+            .collect()
+    }
+
+    pub fn window(&self) -> LeaderWindow {
+        match self.upcoming_leaders() {
+            Some(leaders) if leaders.iter().any(|l| self.jito_validators.contains(l)) => {
+                LeaderWindow::Jito
+            }
+            Some(_) => LeaderWindow::NonJito,
+            None => LeaderWindow::Unknown,
+        }
+    }
+
+    fn set_slot(&self, slot: u64) {
+        self.slot.fetch_max(slot, Ordering::Relaxed);
+        *self.slot_updated_at.write().unwrap() = Some(Instant::now());
+    }
+
+    fn slot_is_stale(&self) -> bool {
+        match *self.slot_updated_at.read().unwrap() {
+            Some(at) => at.elapsed() > SLOT_STALE_AFTER,
+            None => true,
+        }
+    }
+
+    async fn slot_loop(self: Arc<Self>, ws_rpc: String) {
+        loop {
+            match PubsubClient::new(&ws_rpc).await {
+                Ok(client) => match client.slot_subscribe().await {
+                    Ok((mut slots, _unsubscribe)) => {
+                        info!("Leader tracker subscribed to slots");
+                        while let Some(update) = slots.next().await {
+                            self.set_slot(update.slot);
+                        }
+                        warn!("slot subscription ended, resubscribing");
+                    }
+                    Err(e) => warn!("slot subscription failed: {}", e),
+                },
+                Err(e) => warn!("cannot connect to {} for slot updates: {}", ws_rpc, e),
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    async fn schedule_loop(self: Arc<Self>, http_rpc: String) {
+        let rpc = RpcClient::new_with_commitment(http_rpc, CommitmentConfig::processed());
+        let mut ticker = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if self.slot_is_stale() {
+                match rpc.get_slot().await {
+                    Ok(slot) => self.set_slot(slot),
+                    Err(e) => {
+                        debug!("cannot poll slot: {}", e);
+                        continue;
+                    }
+                }
+            }
+            let Some(slot) = self.current_slot() else {
+                continue;
+            };
+            if self.schedule.read().unwrap().covers_until() >= slot + SCHEDULE_MARGIN {
+                continue;
+            }
+            match rpc.get_slot_leaders(slot, SCHEDULE_WINDOW).await {
+                Ok(leaders) => {
+                    debug!(
+                        "leader schedule fetched for slots {}..{}",
+                        slot,
+                        slot + leaders.len() as u64
+                    );
+                    *self.schedule.write().unwrap() = Schedule {
+                        first_slot: slot,
+                        leaders,
+                    };
+                }
+                Err(e) => warn!("cannot fetch leader schedule: {}", e),
+            }
+        }
+    }
+}
@@ -1,22 +1,33 @@
 use crate::geyser::GeyserResult;
+use anyhow::Context;
+use base64::{engine::general_purpose, Engine as _};
+use bincode::config::standard as bincode_standard_config;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
 use solana_sdk::instruction::AccountMeta;
 use solana_sdk::message::v0::{LoadedAddresses, LoadedMessage};
 use solana_sdk::message::VersionedMessage;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::reserved_account_keys::ReservedAccountKeys;
+use solana_sdk::signature::Signature;
 use solana_sdk::transaction::VersionedTransaction;
-use solana_transaction_status::TransactionStatusMeta;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, TransactionStatusMeta,
+    UiLoadedAddresses, UiTransactionEncoding, UiTransactionStatusMeta,
+};
+use std::str::FromStr;
 
+pub mod blockhash;
+pub mod leaders;
+
+/// Resolves the top-level instructions against the static and loaded account keys.
 pub fn extract_instructions(
-    meta_data: TransactionStatusMeta,
-    transaction: VersionedTransaction,
+    meta: &TransactionStatusMeta,
+    transaction: &VersionedTransaction,
 ) -> GeyserResult<Vec<solana_sdk::instruction::Instruction>> {
-    let message = transaction.message.clone();
-    let meta = meta_data.clone();
-
     let mut instructions = Vec::<solana_sdk::instruction::Instruction>::new();
 
-    match message {
+    match &transaction.message {
         VersionedMessage::Legacy(legacy) => {
             for (_i, compiled_instruction) in legacy.instructions.iter().enumerate() {
                 let program_id = *legacy
@@ -45,24 +56,9 @@ pub fn extract_instructions(
             }
         }
         VersionedMessage::V0(v0) => {
-            let loaded_addresses = LoadedAddresses {
-                writable: meta
-                    .loaded_addresses
-                    .writable
-                    .iter()
-                    .map(|key| key.clone())
-                    .collect(),
-                readonly: meta
-                    .loaded_addresses
-                    .readonly
-                    .iter()
-                    .map(|key| key.clone())
-                    .collect(),
-            };
-
-            let loaded_message = LoadedMessage::new(
-                v0.clone(),
-                loaded_addresses,
+            let loaded_message = LoadedMessage::new_borrowed(
+                v0,
+                &meta.loaded_addresses,
                 &ReservedAccountKeys::empty_key_set(),
             );
 
@@ -98,3 +94,74 @@ pub fn extract_instructions(
 
     Ok(instructions)
 }
+
+/// Fetches a confirmed transaction over RPC and decodes it into the same shape geyser delivers.
+pub async fn fetch_transaction(
+    rpc: &RpcClient,
+    signature: &Signature,
+) -> anyhow::Result<(VersionedTransaction, TransactionStatusMeta)> {
+    let tx: EncodedConfirmedTransactionWithStatusMeta = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .context("RPC get_transaction failed")?;
+
+    // Decode transaction
+    let encoded_tx = match &tx.transaction.transaction {
+        EncodedTransaction::Binary(bin, _) => bin,
+        _ => anyhow::bail!("transaction encoding is not binary"),
+    };
+    let tx_bytes = general_purpose::STANDARD
+        .decode(encoded_tx)
+        .context("base64 decode failed")?;
+    let (versioned_tx, _): (VersionedTransaction, usize) =
+        bincode::serde::decode_from_slice(&tx_bytes, bincode_standard_config())
+            .context("bincode deserialize failed")?;
+
+    let ui_meta: UiTransactionStatusMeta = tx
+        .transaction
+        .meta
+        .context("missing meta in RPC response")?;
+
+    // Construct TransactionStatusMeta from UI meta
+    let meta = TransactionStatusMeta {
+        status: ui_meta.err.map_or(Ok(()), Err),
+        fee: ui_meta.fee,
+        pre_balances: ui_meta.pre_balances,
+        post_balances: ui_meta.post_balances,
+        inner_instructions: None,
+        log_messages: ui_meta.log_messages.into(),
+        pre_token_balances: None,
+        post_token_balances: None,
+        rewards: ui_meta.rewards.into(),
+        loaded_addresses: {
+            let opt_ui_loaded_addresses: Option<UiLoadedAddresses> =
+                ui_meta.loaded_addresses.into();
+            match opt_ui_loaded_addresses {
+                Some(ui_loaded) => LoadedAddresses {
+                    writable: ui_loaded
+                        .writable
+                        .into_iter()
+                        .map(|s| Pubkey::from_str(&s))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    readonly: ui_loaded
+                        .readonly
+                        .into_iter()
+                        .map(|s| Pubkey::from_str(&s))
+                        .collect::<Result<Vec<_>, _>>()?,
+                },
+                None => LoadedAddresses::default(),
+            }
+        },
+        return_data: None,
+        compute_units_consumed: ui_meta.compute_units_consumed.into(),
+    };
+
+    Ok((versioned_tx, meta))
+}
@@ -0,0 +1,202 @@
+use crate::config::{CaptureConfig, CaptureMode};
+use crate::geyser::dispatch::DispatchHandle;
+
+use anyhow::Context;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use yellowstone_grpc_proto::geyser::{subscribe_update::UpdateOneof, SubscribeUpdate};
+use yellowstone_grpc_proto::prost::Message;
+
+/// First bytes of every capture file.
+pub const MAGIC: &[u8; 8] = b"GEYCAP01";
+/// Bytes before the payload of a record: length, receive time, endpoint.
+pub const RECORD_HEADER_LEN: usize = 4 + 8 + 2;
+
+struct Record {
+    received_at_us: u64,
+    endpoint: u16,
+    update: UpdateOneof,
+}
+
+/// Feeds the capture writer; recording never blocks the stream, records that do not fit
+/// in the queue are dropped and counted.
+#[derive(Clone, Debug)]
+pub struct Capture {
+    mode: CaptureMode,
+    records: mpsc::Sender<Record>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Capture {
+    /// Starts the writer thread.
+    pub fn spawn(config: CaptureConfig) -> anyhow::Result<Self> {
+        fs::create_dir_all(&config.dir)
+            .with_context(|| format!("cannot create capture dir {}", config.dir))?;
+        let (records, queue) = mpsc::channel(config.queue_capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let capture = Self {
+            mode: config.mode,
+            records,
+            dropped: dropped.clone(),
+        };
+        tokio::task::spawn_blocking(move || write_loop(config, queue, dropped));
+        Ok(capture)
+    }
+
+    /// Records an update as received from `endpoint` at `received_at`, before
+    /// deduplication. In `transactions` mode only transactions some handler wants are kept.
+    pub fn record(
+        &self,
+        endpoint: usize,
+        received_at: SystemTime,
+        update: &UpdateOneof,
+        dispatch: &DispatchHandle,
+    ) {
+        if self.mode == CaptureMode::Transactions {
+            let UpdateOneof::Transaction(transaction) = update else {
+                return;
+            };
+            let wanted = transaction
+                .transaction
+                .as_ref()
+                .and_then(|info| info.transaction.as_ref())
+                .is_some_and(|raw| dispatch.wants(raw));
+            if !wanted {
+                return;
+            }
+        }
+        let record = Record {
+            received_at_us: unix_micros(received_at),
+            endpoint: endpoint as u16,
+            update: update.clone(),
+        };
+        if self.records.try_send(record).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn unix_micros(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64
+}
+
+/// One capture file being written.
+struct CaptureFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    opened_at: Instant,
+    bytes: u64,
+    records: u64,
+}
+
+impl CaptureFile {
+    fn create(dir: &str) -> anyhow::Result<Self> {
+        let path = PathBuf::from(dir).join(format!(
+            "geyser-{}.cap",
+            unix_micros(SystemTime::now()) / 1000
+        ));
+        let file =
+            File::create(&path).with_context(|| format!("cannot create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(MAGIC)?;
+        info!("Capturing Geyser updates to {}", path.display());
+        Ok(Self {
+            path,
+            writer,
+            opened_at: Instant::now(),
+            bytes: MAGIC.len() as u64,
+            records: 0,
+        })
+    }
+
+    /// `[payload length: u32][received at, unix µs: u64][endpoint: u16][SubscribeUpdate]`,
+    /// integers little-endian.
+    fn write(&mut self, record: Record) -> std::io::Result<()> {
+        let payload = SubscribeUpdate {
+            filters: vec![],
+            update_oneof: Some(record.update),
+            created_at: None,
+        }
+        .encode_to_vec();
+        self.writer
+            .write_all(&(payload.len() as u32).to_le_bytes())?;
+        self.writer
+            .write_all(&record.received_at_us.to_le_bytes())?;
+        self.writer.write_all(&record.endpoint.to_le_bytes())?;
+        self.writer.write_all(&payload)?;
+        self.bytes += (RECORD_HEADER_LEN + payload.len()) as u64;
+        self.records += 1;
+        Ok(())
+    }
+
+    fn close(mut self, dropped: u64) {
+        if let Err(e) = self.writer.flush() {
+            warn!("cannot flush {}: {}", self.path.display(), e);
+        }
+        info!(
+            "Closed capture {} ({} records, {} bytes, {} dropped)",
+            self.path.display(),
+            self.records,
+            self.bytes,
+            dropped
+        );
+    }
+}
+
+fn write_loop(config: CaptureConfig, mut queue: mpsc::Receiver<Record>, dropped: Arc<AtomicU64>) {
+    let max_bytes = config.max_file_mb.max(1) * 1024 * 1024;
+    let rotate_after = Duration::from_secs(config.rotate_secs);
+    let mut file: Option<CaptureFile> = None;
+
+    while let Some(record) = queue.blocking_recv() {
+        let mut next = Some(record);
+        // Drain whatever queued up, then flush once.
+        while let Some(record) = next.take().or_else(|| queue.try_recv().ok()) {
+            let rotate = file.as_ref().is_some_and(|current| {
+                current.bytes >= max_bytes
+                    || (!rotate_after.is_zero() && current.opened_at.elapsed() >= rotate_after)
+            });
+            if rotate {
+                if let Some(current) = file.take() {
+                    current.close(dropped.swap(0, Ordering::Relaxed));
+                }
+            }
+            if file.is_none() {
+                match CaptureFile::create(&config.dir) {
+                    Ok(created) => file = Some(created),
+                    Err(e) => {
+                        warn!("capture stopped: {:#}", e);
+                        return;
+                    }
+                }
+            }
+            let Some(current) = file.as_mut() else {
+                continue;
+            };
+            if let Err(e) = current.write(record) {
+                warn!(
+                    "capture stopped, cannot write {}: {}",
+                    current.path.display(),
+                    e
+                );
+                return;
+            }
+        }
+        if let Some(current) = file.as_mut() {
+            if let Err(e) = current.writer.flush() {
+                warn!("cannot flush {}: {}", current.path.display(), e);
+            }
+        }
+    }
+    if let Some(current) = file.take() {
+        current.close(dropped.swap(0, Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::config::DispatchConfig;
+use crate::geyser::handler::{
+    CompiledFilter, DecodedTransaction, PriorityCheck, RawCheck, TransactionHandler,
+};
+
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+use yellowstone_grpc_proto::geyser::SubscribeRequestFilterTransactions;
+use yellowstone_grpc_proto::solana::storage::confirmed_block::Transaction as RawTransaction;
+
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+enum Job {
+    Transaction(Arc<DecodedTransaction>),
+    BlockMeta {
+        slot: u64,
+        blockhash: String,
+        block_height: Option<u64>,
+    },
+    SlotConfirmed(u64),
+}
+
+struct Queued {
+    job: Job,
+    /// May be evicted when the queue is full.
+    droppable: bool,
+    enqueued_at: Instant,
+}
+
+/// Bounded queue in front of one handler. When full, the oldest droppable job makes room;
+/// jobs that are not droppable are always queued, even past the capacity.
+struct WorkerQueue {
+    capacity: usize,
+    jobs: Mutex<VecDeque<Queued>>,
+    ready: Notify,
+    /// Set from taking a job until the handler is done with it.
+    busy: AtomicBool,
+    max_depth: AtomicUsize,
+    dropped: AtomicU64,
+    handled: AtomicU64,
+    wait_ms: AtomicU64,
+}
+
+impl WorkerQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            jobs: Mutex::new(VecDeque::new()),
+            ready: Notify::new(),
+            busy: AtomicBool::new(false),
+            max_depth: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+            handled: AtomicU64::new(0),
+            wait_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `false` if the job itself was dropped.
+    fn push(&self, job: Job, droppable: bool) -> bool {
+        let depth = {
+            let mut jobs = self.jobs.lock().unwrap();
+            if jobs.len() >= self.capacity {
+                match jobs.iter().position(|queued| queued.droppable) {
+                    Some(oldest) => {
+                        jobs.remove(oldest);
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None if droppable => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        return false;
+                    }
+                    None => {}
+                }
+            }
+            jobs.push_back(Queued {
+                job,
+                droppable,
+                enqueued_at: Instant::now(),
+            });
+            jobs.len()
+        };
+        self.max_depth.fetch_max(depth, Ordering::Relaxed);
+        self.ready.notify_one();
+        true
+    }
+
+    async fn pop(&self) -> Queued {
+        loop {
+            {
+                let mut jobs = self.jobs.lock().unwrap();
+                if let Some(queued) = jobs.pop_front() {
+                    self.busy.store(true, Ordering::Relaxed);
+                    return queued;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    fn idle(&self) -> bool {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.is_empty() && !self.busy.load(Ordering::Relaxed)
+    }
+
+    fn depth(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+}
+
+struct Worker {
+    name: String,
+    filter: CompiledFilter,
+    priority: Option<PriorityCheck>,
+    raw_check: Option<RawCheck>,
+    queue: Arc<WorkerQueue>,
+}
+
+/// Collects the handlers of the decoded stream before their workers are started.
+#[derive(Default)]
+pub struct Dispatcher {
+    handlers: Vec<Box<dyn TransactionHandler>>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_handler(mut self, handler: impl TransactionHandler + 'static) -> Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// One Geyser transaction filter per handler, named `<handler>_transaction_filter`.
+    pub fn subscribe_filters(&self) -> HashMap<String, SubscribeRequestFilterTransactions> {
+        self.handlers
+            .iter()
+            .map(|handler| {
+                (
+                    format!("{}_transaction_filter", handler.name()),
+                    handler.filter().subscription(),
+                )
+            })
+            .collect()
+    }
+
+    /// Moves every handler onto its own worker task behind a bounded queue, so a slow
+    /// handler never holds up the stream or the other handlers.
+    pub fn spawn(self, config: &DispatchConfig) -> DispatchHandle {
+        let workers: Vec<Worker> = self
+            .handlers
+            .into_iter()
+            .map(|handler| {
+                let worker = Worker {
+                    name: handler.name().to_string(),
+                    filter: CompiledFilter::new(&handler.filter()),
+                    priority: handler.priority(),
+                    raw_check: handler.raw_prefilter(),
+                    queue: Arc::new(WorkerQueue::new(config.queue_capacity)),
+                };
+                tokio::spawn(run_worker(handler, worker.queue.clone()));
+                worker
+            })
+            .collect();
+        let handle = DispatchHandle {
+            workers: Arc::new(workers),
+        };
+        if config.metrics_interval_secs > 0 {
+            tokio::spawn(
+                handle
+                    .clone()
+                    .report_loop(Duration::from_secs(config.metrics_interval_secs)),
+            );
+        }
+        handle
+    }
+}
+
+async fn run_worker(mut handler: Box<dyn TransactionHandler>, queue: Arc<WorkerQueue>) {
+    loop {
+        let queued = queue.pop().await;
+        let waited = queued.enqueued_at.elapsed().as_millis() as u64;
+        queue.wait_ms.fetch_add(waited, Ordering::Relaxed);
+        match queued.job {
+            Job::Transaction(transaction) => {
+                if let Err(e) = handler.handle_transaction(&transaction).await {
+                    warn!(
+                        "{} failed on {}: {:#}",
+                        handler.name(),
+                        transaction.signature,
+                        e
+                    );
+                }
+            }
+            Job::BlockMeta {
+                slot,
+                blockhash,
+                block_height,
+            } => handler.on_block_meta(slot, &blockhash, block_height),
+            Job::SlotConfirmed(slot) => handler.on_slot_confirmed(slot),
+        }
+        queue.handled.fetch_add(1, Ordering::Relaxed);
+        queue.busy.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Feeds the handler queues; cheap to clone, never waits on a handler.
+#[derive(Clone)]
+pub struct DispatchHandle {
+    workers: Arc<Vec<Worker>>,
+}
+
+impl DispatchHandle {
+    /// Whether any handler may want the transaction, judged before decoding it.
+    pub fn wants(&self, transaction: &RawTransaction) -> bool {
+        self.workers.iter().any(|worker| match worker.raw_check {
+            Some(check) => check(transaction),
+            None => true,
+        })
+    }
+
+    /// Queues the transaction for every handler whose filter matches it.
+    pub fn dispatch(&self, transaction: DecodedTransaction) {
+        let transaction = Arc::new(transaction);
+        for worker in self.workers.iter() {
+            if !worker.filter.matches(&transaction) {
+                continue;
+            }
+            let droppable = !worker.priority.is_some_and(|check| check(&transaction));
+            let job = Job::Transaction(transaction.clone());
+            if !worker.queue.push(job, droppable) {
+                debug!(
+                    "{} queue full, dropped {}",
+                    worker.name, transaction.signature
+                );
+            }
+        }
+    }
+
+    pub fn block_meta(&self, slot: u64, blockhash: &str, block_height: Option<u64>) {
+        for worker in self.workers.iter() {
+            let job = Job::BlockMeta {
+                slot,
+                blockhash: blockhash.to_string(),
+                block_height,
+            };
+            worker.queue.push(job, false);
+        }
+    }
+
+    pub fn slot_confirmed(&self, slot: u64) {
+        for worker in self.workers.iter() {
+            worker.queue.push(Job::SlotConfirmed(slot), false);
+        }
+    }
+
+    /// Waits until every queue is empty and no handler is working on a job.
+    #[allow(dead_code)]
+    pub async fn drained(&self) {
+        while !self.workers.iter().all(|worker| worker.queue.idle()) {
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Logs one queue record per handler and resets the depth high-water mark.
+    async fn report_loop(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            for worker in self.workers.iter() {
+                let queue = &worker.queue;
+                let depth = queue.depth();
+                let handled = queue.handled.swap(0, Ordering::Relaxed);
+                let wait_ms = queue.wait_ms.swap(0, Ordering::Relaxed);
+                let dropped = queue.dropped.swap(0, Ordering::Relaxed);
+                let avg_wait_ms = if handled > 0 {
+                    wait_ms as f64 / handled as f64
+                } else {
+                    0.0
+                };
+                if dropped > 0 {
+                    warn!("{} queue dropped {} transactions", worker.name, dropped);
+                }
+                info!(
+                    target: "metrics",
+                    "{}",
+                    json!({
+                        "handler": worker.name,
+                        "queue_depth": depth,
+                        "max_queue_depth": queue.max_depth.swap(depth, Ordering::Relaxed),
+                        "handled": handled,
+                        "dropped": dropped,
+                        "avg_wait_ms": avg_wait_ms,
+                    })
+                );
+            }
+        }
+    }
+}
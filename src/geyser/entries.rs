@@ -0,0 +1,130 @@
+use crate::config::EntriesConfig;
+use crate::geyser::dispatch::DispatchHandle;
+use crate::geyser::handler::DecodedTransaction;
+use crate::meteora::controller::invokes_init_pool;
+
+use anyhow::Context;
+use bincode::config;
+use bincode::serde as bincode_serde;
+use futures::StreamExt;
+use serde::Deserialize;
+use solana_sdk::hash::Hash;
+use solana_sdk::transaction::VersionedTransaction;
+use std::time::Duration;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tracing::{debug, info, warn};
+
+/// Delay before the entry stream is re-opened.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Hand-written subset of the ShredStream proxy protos (`shredstream`).
+mod proto {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SubscribeEntriesRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Entry {
+        #[prost(uint64, tag = "1")]
+        pub slot: u64,
+        /// Bincode-encoded `Vec<solana_entry::entry::Entry>`.
+        #[prost(bytes = "vec", tag = "2")]
+        pub entries: Vec<u8>,
+    }
+}
+
+const SUBSCRIBE_ENTRIES: &str = "/shredstream.ShredstreamProxy/SubscribeEntries";
+
+/// Layout of `solana_entry::entry::Entry`.
+#[derive(Deserialize)]
+struct Entry {
+    #[allow(dead_code)]
+    num_hashes: u64,
+    #[allow(dead_code)]
+    hash: Hash,
+    transactions: Vec<VersionedTransaction>,
+}
+
+/// Event source reading ledger entries from a ShredStream proxy: transactions as the
+/// leader broadcasts them, before the slot completes and before they are executed.
+/// Only pool initializations are passed on, without meta.
+pub struct EntrySource {
+    config: EntriesConfig,
+    channel: Channel,
+}
+
+impl EntrySource {
+    pub fn new(config: EntriesConfig) -> anyhow::Result<Self> {
+        let mut endpoint = Endpoint::from_shared(config.url.clone())?.tcp_nodelay(true);
+        // Proxies usually run next to the bot over plain HTTP/2.
+        if config.url.starts_with("https://") {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new().with_enabled_roots())?;
+        }
+        Ok(Self {
+            config,
+            channel: endpoint.connect_lazy(),
+        })
+    }
+
+    /// Keeps the entry stream open for the lifetime of the process.
+    pub async fn run(self, dispatch: DispatchHandle) {
+        loop {
+            if let Err(e) = self.subscribe_once(&dispatch).await {
+                warn!("entry stream from {} failed: {:#}", self.config.url, e);
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    async fn subscribe_once(&self, dispatch: &DispatchHandle) -> anyhow::Result<()> {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready().await?;
+        let mut stream = grpc
+            .server_streaming(
+                tonic::Request::new(proto::SubscribeEntriesRequest {}),
+                PathAndQuery::from_static(SUBSCRIBE_ENTRIES),
+                ProstCodec::<proto::SubscribeEntriesRequest, proto::Entry>::default(),
+            )
+            .await?
+            .into_inner();
+        info!("Subscribed to entries on {}", self.config.url);
+
+        while let Some(message) = stream.next().await {
+            let message = message?;
+            let entries = match decode_entries(&message.entries) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    debug!("undecodable entries in slot {}: {:#}", message.slot, e);
+                    continue;
+                }
+            };
+            for transaction in entries.into_iter().flat_map(|entry| entry.transactions) {
+                if !invokes_init_pool(&transaction) {
+                    continue;
+                }
+                let Some(signature) = transaction.signatures.first().copied() else {
+                    continue;
+                };
+                info!(
+                    "Init-pool tx {} seen in entries of slot {}",
+                    signature, message.slot
+                );
+                dispatch.dispatch(DecodedTransaction {
+                    signature,
+                    transaction,
+                    meta: None,
+                    is_vote: false,
+                    slot: message.slot,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn decode_entries(bytes: &[u8]) -> anyhow::Result<Vec<Entry>> {
+    let (entries, _) = bincode_serde::decode_from_slice(bytes, config::legacy())
+        .context("cannot deserialize entries")?;
+    Ok(entries)
+}
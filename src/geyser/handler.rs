@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::TransactionStatusMeta;
+use std::collections::HashSet;
+use yellowstone_grpc_proto::geyser::SubscribeRequestFilterTransactions;
+use yellowstone_grpc_proto::solana::storage::confirmed_block::Transaction as RawTransaction;
+
+/// A transaction decoded from the stream, shared by every handler it matches.
+pub struct DecodedTransaction {
+    pub signature: Signature,
+    pub transaction: VersionedTransaction,
+    /// `None` for transactions seen before execution (entry stream): no status, no
+    /// addresses loaded from lookup tables.
+    pub meta: Option<TransactionStatusMeta>,
+    pub is_vote: bool,
+    pub slot: u64,
+}
+
+impl DecodedTransaction {
+    /// Static account keys followed by the addresses loaded from lookup tables, if known.
+    pub fn account_keys(&self) -> impl Iterator<Item = &Pubkey> {
+        let loaded = self.meta.as_ref().map(|meta| &meta.loaded_addresses);
+        self.transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .chain(loaded.into_iter().flat_map(|loaded| &loaded.writable))
+            .chain(loaded.into_iter().flat_map(|loaded| &loaded.readonly))
+    }
+}
+
+/// Which transactions a handler wants; also sent to Geyser as a subscription filter.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    /// Transactions touching any of these accounts; empty matches all.
+    pub account_include: Vec<Pubkey>,
+    /// Transactions touching any of these accounts are skipped.
+    pub account_exclude: Vec<Pubkey>,
+    pub include_votes: bool,
+    pub include_failed: bool,
+}
+
+impl TransactionFilter {
+    pub(super) fn subscription(&self) -> SubscribeRequestFilterTransactions {
+        SubscribeRequestFilterTransactions {
+            vote: (!self.include_votes).then_some(false),
+            failed: (!self.include_failed).then_some(false),
+            account_include: self.account_include.iter().map(|k| k.to_string()).collect(),
+            account_exclude: self.account_exclude.iter().map(|k| k.to_string()).collect(),
+            account_required: vec![],
+            signature: None,
+        }
+    }
+}
+
+/// A consumer of the decoded transaction stream (sniper, exit engine, recorder, ...).
+#[async_trait]
+pub trait TransactionHandler: Send {
+    /// Names the handler in logs and in its Geyser subscription filter.
+    fn name(&self) -> &str;
+
+    fn filter(&self) -> TransactionFilter;
+
+    /// Runs on the stream task before queueing. Transactions it accepts are never dropped
+    /// when the handler's queue is full; the rest may be.
+    fn priority(&self) -> Option<PriorityCheck> {
+        None
+    }
+
+    /// Runs on the protobuf transaction before it is converted to Solana types. Only when
+    /// every handler has a prefilter and all of them reject a transaction is it dropped
+    /// unconverted.
+    fn raw_prefilter(&self) -> Option<RawCheck> {
+        None
+    }
+
+    async fn handle_transaction(&mut self, transaction: &DecodedTransaction) -> anyhow::Result<()>;
+
+    /// Block-meta update of any slot.
+    fn on_block_meta(&mut self, _slot: u64, _blockhash: &str, _block_height: Option<u64>) {}
+
+    /// A slot reached `confirmed` commitment.
+    fn on_slot_confirmed(&mut self, _slot: u64) {}
+}
+
+/// Cheap check whether a transaction is one the handler must never miss.
+pub type PriorityCheck = fn(&DecodedTransaction) -> bool;
+
+/// Cheap check on the prost transaction, before conversion, whether the handler can use it.
+pub type RawCheck = fn(&RawTransaction) -> bool;
+
+/// `TransactionFilter` with the account lists turned into sets.
+pub(super) struct CompiledFilter {
+    include: HashSet<Pubkey>,
+    exclude: HashSet<Pubkey>,
+    include_votes: bool,
+    include_failed: bool,
+}
+
+impl CompiledFilter {
+    pub(super) fn new(filter: &TransactionFilter) -> Self {
+        Self {
+            include: filter.account_include.iter().copied().collect(),
+            exclude: filter.account_exclude.iter().copied().collect(),
+            include_votes: filter.include_votes,
+            include_failed: filter.include_failed,
+        }
+    }
+
+    pub(super) fn matches(&self, transaction: &DecodedTransaction) -> bool {
+        if transaction.is_vote && !self.include_votes {
+            return false;
+        }
+        let failed = matches!(&transaction.meta, Some(meta) if meta.status.is_err());
+        if failed && !self.include_failed {
+            return false;
+        }
+        let mut included = self.include.is_empty();
+        for key in transaction.account_keys() {
+            if self.exclude.contains(key) {
+                return false;
+            }
+            included |= self.include.contains(key);
+        }
+        included
+    }
+}
@@ -0,0 +1,157 @@
+use crate::config::LogsFallbackConfig;
+use crate::core::fetch_transaction;
+use crate::geyser::dispatch::DispatchHandle;
+use crate::geyser::handler::DecodedTransaction;
+use crate::meteora::constants::{INIT_POOL_LOG_PREFIX, METEORA_PROGRAM_ID};
+
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Second event source: `logsSubscribe` on the Meteora program over `ws_rpc`. An init-pool
+/// log line triggers a `getTransaction`, and the fetched transaction goes to the same
+/// handlers as Geyser's. Slower than Geyser, since the transaction can only be fetched
+/// once confirmed.
+pub struct LogsSource {
+    config: LogsFallbackConfig,
+    ws_rpc: String,
+    rpc: Arc<RpcClient>,
+    /// Set while a Geyser stream delivers; matches are skipped then. `None` when the
+    /// source runs on its own.
+    geyser_live: Option<Arc<AtomicBool>>,
+}
+
+impl LogsSource {
+    pub fn new(config: LogsFallbackConfig, http_rpc: String, ws_rpc: String) -> Self {
+        Self {
+            config,
+            ws_rpc,
+            // `getTransaction` does not serve processed transactions.
+            rpc: Arc::new(RpcClient::new_with_commitment(
+                http_rpc,
+                CommitmentConfig::confirmed(),
+            )),
+            geyser_live: None,
+        }
+    }
+
+    /// Only act while the Geyser client reports no live stream.
+    pub fn fallback_for(mut self, geyser_live: Arc<AtomicBool>) -> Self {
+        self.geyser_live = Some(geyser_live);
+        self
+    }
+
+    fn active(&self) -> bool {
+        match &self.geyser_live {
+            Some(live) => !live.load(Ordering::Relaxed),
+            None => true,
+        }
+    }
+
+    /// Keeps the subscription open for the lifetime of the process.
+    pub async fn run(self, dispatch: DispatchHandle) {
+        let source = Arc::new(self);
+        let mut was_active = None;
+        loop {
+            match PubsubClient::new(&source.ws_rpc).await {
+                Ok(client) => match client
+                    .logs_subscribe(
+                        RpcTransactionLogsFilter::Mentions(vec![METEORA_PROGRAM_ID.to_string()]),
+                        RpcTransactionLogsConfig {
+                            commitment: Some(CommitmentConfig::processed()),
+                        },
+                    )
+                    .await
+                {
+                    Ok((mut logs, _unsubscribe)) => {
+                        info!("Subscribed to Meteora program logs on {}", source.ws_rpc);
+                        while let Some(response) = logs.next().await {
+                            let active = source.active();
+                            if source.geyser_live.is_some() && was_active != Some(active) {
+                                if active {
+                                    warn!("No Geyser stream live, logsSubscribe source active");
+                                } else if was_active.is_some() {
+                                    info!("Geyser stream live again, logsSubscribe on standby");
+                                }
+                                was_active = Some(active);
+                            }
+                            let notification = response.value;
+                            if !active
+                                || notification.err.is_some()
+                                || !notification
+                                    .logs
+                                    .iter()
+                                    .any(|line| line.starts_with(INIT_POOL_LOG_PREFIX))
+                            {
+                                continue;
+                            }
+                            let Ok(signature) = Signature::from_str(&notification.signature) else {
+                                continue;
+                            };
+                            tokio::spawn(source.clone().fetch_and_dispatch(
+                                signature,
+                                response.context.slot,
+                                dispatch.clone(),
+                            ));
+                        }
+                        warn!("logs subscription ended, resubscribing");
+                    }
+                    Err(e) => warn!("logs subscription failed: {}", e),
+                },
+                Err(e) => warn!("cannot connect to {} for logs: {}", source.ws_rpc, e),
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    /// Polls until the transaction is confirmed, then hands it to the handlers.
+    async fn fetch_and_dispatch(
+        self: Arc<Self>,
+        signature: Signature,
+        slot: u64,
+        dispatch: DispatchHandle,
+    ) {
+        let started = Instant::now();
+        let attempts = self.config.fetch_attempts.max(1);
+        for attempt in 1..=attempts {
+            match fetch_transaction(&self.rpc, &signature).await {
+                Ok((transaction, meta)) => {
+                    info!(
+                        "Init-pool tx {} fetched from logs after {:?}",
+                        signature,
+                        started.elapsed()
+                    );
+                    dispatch.dispatch(DecodedTransaction {
+                        signature,
+                        transaction,
+                        meta: Some(meta),
+                        is_vote: false,
+                        slot,
+                    });
+                    return;
+                }
+                Err(e) if attempt < attempts => {
+                    debug!("{} not fetchable yet ({}): {:#}", signature, attempt, e)
+                }
+                Err(e) => {
+                    warn!(
+                        "giving up on {} after {} attempts: {:#}",
+                        signature, attempts, e
+                    );
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(self.config.fetch_retry_ms)).await;
+        }
+    }
+}
@@ -1,23 +1,30 @@
 use async_trait::async_trait;
-use futures::StreamExt;
+use futures::{Sink, SinkExt, Stream, StreamExt};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use std::collections::HashSet;
 use std::convert::TryFrom;
-use std::time::Duration;
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
-use tracing::{error, info};
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
 use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::tonic::service::Interceptor;
 use yellowstone_grpc_proto::tonic::transport::ClientTlsConfig;
+use yellowstone_grpc_proto::tonic::Status;
 use yellowstone_grpc_proto::{
     convert_from::{create_tx_meta, create_tx_versioned},
     geyser::{
-        subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
-        SubscribeRequestFilterAccounts, SubscribeRequestFilterTransactions,
+        subscribe_update::UpdateOneof, CommitmentLevel, SlotStatus, SubscribeRequest,
+        SubscribeRequestFilterAccounts, SubscribeRequestFilterBlocksMeta,
+        SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+        SubscribeUpdate,
     },
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct YellowstoneGrpcGeyserClient {
     pub endpoint: String,
     pub x_token: Option<String>,
@@ -25,6 +32,14 @@ pub struct YellowstoneGrpcGeyserClient {
     pub account_filters: HashMap<String, SubscribeRequestFilterAccounts>,
     pub transaction_filters: HashMap<String, SubscribeRequestFilterTransactions>,
     pub account_deletions_tracked: Arc<RwLock<HashSet<Pubkey>>>,
+    /// Also subscribe to slots and block-meta to feed the blockhash cache.
+    pub track_blockhash: bool,
+    /// Extra endpoints, reconnect backoff, `from_slot` resumption and deduplication.
+    pub config: GeyserConfig,
+    /// Set while at least one endpoint stream delivers.
+    live: Arc<AtomicBool>,
+    /// Writes received updates to disk.
+    pub capture: Option<Capture>,
 }
 
 impl YellowstoneGrpcGeyserClient {
@@ -44,6 +59,281 @@ impl YellowstoneGrpcGeyserClient {
             account_filters,
             transaction_filters,
             account_deletions_tracked,
+            track_blockhash: false,
+            config: GeyserConfig::default(),
+            live: Arc::new(AtomicBool::new(false)),
+            capture: None,
+        }
+    }
+
+    /// Subscribe to slot and block-meta updates for the blockhash cache.
+    pub fn with_blockhash_tracking(mut self) -> Self {
+        self.track_blockhash = true;
+        self
+    }
+
+    pub fn with_config(mut self, config: GeyserConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_capture(mut self, capture: Capture) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Shared flag telling other event sources whether Geyser currently delivers.
+    pub fn liveness(&self) -> Arc<AtomicBool> {
+        self.live.clone()
+    }
+
+    /// `config.endpoints`, or the single `endpoint` when none are listed.
+    fn endpoints(&self) -> Vec<GeyserEndpoint> {
+        if self.config.endpoints.is_empty() {
+            vec![GeyserEndpoint {
+                name: "geyser".to_string(),
+                url: self.endpoint.clone(),
+                x_token: self.x_token.clone(),
+            }]
+        } else {
+            self.config.endpoints.clone()
+        }
+    }
+
+    /// Opens a fresh channel to the endpoint.
+    async fn connect(&self) -> GeyserResult<GeyserGrpcClient<impl Interceptor>> {
+        GeyserGrpcClient::build_from_shared(self.endpoint.clone())
+            .map_err(|err| Error::Custom(err.to_string()))?
+            .x_token(self.x_token.clone())
+            .map_err(|err| Error::Custom(err.to_string()))?
+            .connect_timeout(Duration::from_secs(15))
+            .timeout(Duration::from_secs(15))
+            .tls_config(ClientTlsConfig::new().with_enabled_roots())
+            .map_err(|err| Error::Custom(err.to_string()))?
+            .connect()
+            .await
+            .map_err(|err| Error::Custom(err.to_string()))
+    }
+
+    fn subscribe_request(&self, from_slot: Option<u64>) -> SubscribeRequest {
+        let mut slot_filters = HashMap::new();
+        let mut blocks_meta_filters = HashMap::new();
+        // Slots feed the blockhash cache and the stall watchdog.
+        if self.track_blockhash || self.config.stall_timeout_secs > 0 {
+            slot_filters.insert(
+                "slots".to_string(),
+                SubscribeRequestFilterSlots {
+                    filter_by_commitment: Some(false),
+                    interslot_updates: Some(false),
+                },
+            );
+        }
+        if self.track_blockhash {
+            blocks_meta_filters.insert(
+                "blockhash_blocks_meta".to_string(),
+                SubscribeRequestFilterBlocksMeta {},
+            );
+        }
+
+        SubscribeRequest {
+            slots: slot_filters,
+            accounts: self.account_filters.clone(),
+            transactions: self.transaction_filters.clone(),
+            transactions_status: HashMap::new(),
+            entry: HashMap::new(),
+            blocks: HashMap::new(),
+            blocks_meta: blocks_meta_filters,
+            commitment: self.commitment.map(|x| x as i32),
+            accounts_data_slice: vec![],
+            ping: None,
+            from_slot,
+        }
+    }
+
+    /// Keeps one endpoint subscribed for the lifetime of the process, forwarding its updates.
+    /// Every stream error or end is followed by a fresh channel and subscription after an
+    /// exponential backoff. Returns once the consumer is gone.
+    async fn run_stream(self, index: usize, events: mpsc::Sender<StreamEvent>) {
+        let mut backoff = Backoff::new(&self.config);
+        let mut last_slot: Option<u64> = None;
+        let mut resume = self.config.resume_from_slot;
+
+        loop {
+            let from_slot = if resume { last_slot } else { None };
+            let mut subscribed = false;
+            let mut received = 0u64;
+
+            match self.connect().await {
+                Ok(mut geyser_client) => {
+                    info!(target: "geyser", "✅ Connected to Geyser endpoint {}", self.endpoint);
+                    match geyser_client
+                        .subscribe_with_request(Some(self.subscribe_request(from_slot)))
+                        .await
+                    {
+                        Ok((subscribe_tx, stream)) => {
+                            subscribed = true;
+                            if let Some(slot) = from_slot {
+                                info!(
+                                    target: "geyser",
+                                    "Resuming {} from slot {slot}",
+                                    self.endpoint
+                                );
+                            }
+                            let end = self
+                                .read_stream(
+                                    index,
+                                    subscribe_tx,
+                                    stream,
+                                    &events,
+                                    &mut received,
+                                    &mut last_slot,
+                                    &mut backoff,
+                                )
+                                .await;
+                            if let StreamEnd::ConsumerGone = end {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("Failed to subscribe to {}: {:?}", self.endpoint, e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    log::error!("Failed to connect to {}: {e}", self.endpoint);
+                }
+            }
+
+            if received > 0 && events.send(StreamEvent::Disconnected(index)).await.is_err() {
+                return;
+            }
+            // A provider that cannot replay that far back fails the stream right away;
+            // subscribe live instead of retrying the same slot forever.
+            if subscribed && received == 0 && from_slot.is_some() {
+                warn!(
+                    target: "geyser",
+                    "Resumption from slot {:?} rejected by {}, subscribing live",
+                    from_slot,
+                    self.endpoint
+                );
+                resume = false;
+            } else if received > 0 {
+                resume = self.config.resume_from_slot;
+            }
+
+            let delay = backoff.next_delay();
+            warn!(
+                target: "geyser",
+                "Geyser stream {} lost, reconnecting in {:?} (attempt {})",
+                self.endpoint,
+                delay,
+                backoff.attempt()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Forwards updates until the stream fails, ends or stalls. Server pings are answered
+    /// and the client pings on its own every `ping_interval_secs`, so idle streams are not
+    /// dropped by load balancers; a stream whose slot does not advance for
+    /// `stall_timeout_secs` is treated as dead.
+    #[allow(clippy::too_many_arguments)]
+    async fn read_stream<S, T>(
+        &self,
+        index: usize,
+        subscribe_tx: S,
+        stream: T,
+        events: &mpsc::Sender<StreamEvent>,
+        received: &mut u64,
+        last_slot: &mut Option<u64>,
+        backoff: &mut Backoff,
+    ) -> StreamEnd
+    where
+        S: Sink<SubscribeRequest>,
+        S::Error: std::fmt::Debug,
+        T: Stream<Item = Result<SubscribeUpdate, Status>>,
+    {
+        let mut subscribe_tx = pin!(subscribe_tx);
+        let mut stream = pin!(stream);
+
+        let ping_enabled = self.config.ping_interval_secs > 0;
+        let mut ping =
+            tokio::time::interval(Duration::from_secs(self.config.ping_interval_secs.max(1)));
+        ping.tick().await;
+        let stall_timeout = Duration::from_secs(self.config.stall_timeout_secs);
+        let mut watchdog = tokio::time::interval(WATCHDOG_INTERVAL);
+        let mut progress_at = Instant::now();
+
+        loop {
+            tokio::select! {
+                message = stream.next() => {
+                    let msg = match message {
+                        Some(Ok(msg)) => msg,
+                        Some(Err(error)) => {
+                            log::error!("Geyser stream error on {}: {error:?}", self.endpoint);
+                            return StreamEnd::Lost;
+                        }
+                        None => return StreamEnd::Lost,
+                    };
+                    let received_at = Instant::now();
+                    let received_wall = SystemTime::now();
+                    if *received == 0 {
+                        backoff.reset();
+                        if events.send(StreamEvent::Connected(index)).await.is_err() {
+                            return StreamEnd::ConsumerGone;
+                        }
+                    }
+                    *received += 1;
+                    let Some(update) = msg.update_oneof else {
+                        continue;
+                    };
+                    if let Some(slot) = update_slot(&update) {
+                        if last_slot.is_none_or(|last| slot > last) {
+                            *last_slot = Some(slot);
+                            progress_at = received_at;
+                        }
+                    }
+                    match update {
+                        UpdateOneof::Ping(_) => {
+                            if let Err(e) = subscribe_tx.send(ping_request()).await {
+                                log::error!("Cannot answer ping of {}: {e:?}", self.endpoint);
+                                return StreamEnd::Lost;
+                            }
+                            continue;
+                        }
+                        UpdateOneof::Pong(_) => continue,
+                        // Subscribed for the watchdog only.
+                        UpdateOneof::Slot(_) if !self.track_blockhash => continue,
+                        _ => {}
+                    }
+                    let event = StreamEvent::Update {
+                        endpoint: index,
+                        received_at,
+                        received_wall,
+                        update,
+                    };
+                    if events.send(event).await.is_err() {
+                        return StreamEnd::ConsumerGone;
+                    }
+                }
+                _ = ping.tick(), if ping_enabled => {
+                    if let Err(e) = subscribe_tx.send(ping_request()).await {
+                        log::error!("Cannot ping {}: {e:?}", self.endpoint);
+                        return StreamEnd::Lost;
+                    }
+                }
+                _ = watchdog.tick(), if !stall_timeout.is_zero() => {
+                    if progress_at.elapsed() > stall_timeout {
+                        warn!(
+                            target: "geyser",
+                            "No slot progress on {} for {:?}, forcing reconnect",
+                            self.endpoint,
+                            stall_timeout
+                        );
+                        return StreamEnd::Lost;
+                    }
+                }
+            }
         }
     }
 }
@@ -53,136 +343,205 @@ pub type GeyserResult<T> = Result<T, Error>;
 #[async_trait]
 pub trait YellowstoneGrpcGeyser: Send + Sync {
     #[allow(dead_code)]
-    async fn consume(&self, meteora_controller: MeteoraController) -> GeyserResult<()>;
+    async fn consume(&self, dispatch: DispatchHandle) -> GeyserResult<()>;
 }
 
-use crate::meteora::controller::MeteoraController;
+use crate::config::{GeyserConfig, GeyserEndpoint};
 use thiserror::Error;
 
+pub mod capture;
+pub mod dispatch;
+pub mod entries;
+pub mod handler;
+pub mod logs;
+mod race;
+mod reconnect;
+// Only used by the replay tool.
+#[allow(dead_code)]
+pub mod replay;
+
+use capture::Capture;
+use dispatch::DispatchHandle;
+use handler::DecodedTransaction;
+
+use race::Race;
+use reconnect::Backoff;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Custom error: {0}")]
     Custom(String),
 }
 
+/// Updates merged from all endpoint streams.
+const EVENT_QUEUE: usize = 10_000;
+/// How often a stream's slot progress is checked.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Why `read_stream` returned.
+enum StreamEnd {
+    /// Failed, ended or stalled; reconnect.
+    Lost,
+    /// `consume` is gone; stop.
+    ConsumerGone,
+}
+
+/// Ping-only request: answers a server ping or keeps the stream busy, filters unchanged.
+fn ping_request() -> SubscribeRequest {
+    SubscribeRequest {
+        ping: Some(SubscribeRequestPing { id: 1 }),
+        ..Default::default()
+    }
+}
+
+enum StreamEvent {
+    /// The endpoint's stream delivers updates again.
+    Connected(usize),
+    Disconnected(usize),
+    Update {
+        endpoint: usize,
+        received_at: Instant,
+        /// Wall-clock receive time, for captures; taken before the event queue.
+        received_wall: SystemTime,
+        update: UpdateOneof,
+    },
+}
+
+fn update_slot(update: &UpdateOneof) -> Option<u64> {
+    match update {
+        UpdateOneof::Transaction(tx) => Some(tx.slot),
+        UpdateOneof::Slot(slot) => Some(slot.slot),
+        UpdateOneof::BlockMeta(meta) => Some(meta.slot),
+        _ => None,
+    }
+}
+
 #[async_trait]
 impl YellowstoneGrpcGeyser for YellowstoneGrpcGeyserClient {
-    async fn consume(&self, mut meteora_controller: MeteoraController) -> GeyserResult<()> {
-        let endpoint = self.endpoint.clone();
-        let x_token = self.x_token.clone();
-        let commitment = self.commitment;
-        let account_filters = self.account_filters.clone();
-        let transaction_filters = self.transaction_filters.clone();
-        let _account_deletions_tracked = self.account_deletions_tracked.clone();
-
-        let mut geyser_client = GeyserGrpcClient::build_from_shared(endpoint.clone())
-            .map_err(|err| Error::Custom(err.to_string()))?
-            .x_token(x_token.clone())
-            .map_err(|err| Error::Custom(err.to_string()))?
-            .connect_timeout(Duration::from_secs(15))
-            .timeout(Duration::from_secs(15))
-            .tls_config(ClientTlsConfig::new().with_enabled_roots())
-            .map_err(|err| Error::Custom(err.to_string()))?
-            .connect()
-            .await
-            .map_err(|err| Error::Custom(err.to_string()))?;
-
-        info!(target: "geyser", "✅ Connected to Geyser endpoint {endpoint}");
-
-        let _ = tokio::spawn(async move {
-            let subscribe_request = SubscribeRequest {
-                slots: HashMap::new(),
-                accounts: account_filters,
-                transactions: transaction_filters,
-                transactions_status: HashMap::new(),
-                entry: HashMap::new(),
-                blocks: HashMap::new(),
-                blocks_meta: HashMap::new(),
-                commitment: commitment.map(|x| x as i32),
-                accounts_data_slice: vec![],
-                ping: None,
-                from_slot: None,
+    /// Subscribes to every endpoint at once and handles each transaction from whichever
+    /// stream delivers it first. Runs until the process exits.
+    async fn consume(&self, dispatch: DispatchHandle) -> GeyserResult<()> {
+        let endpoints = self.endpoints();
+        let mut race = Race::new(
+            endpoints.iter().map(|e| e.name.clone()).collect(),
+            self.config.dedup_capacity,
+        );
+
+        let (events_tx, mut events) = mpsc::channel(EVENT_QUEUE);
+        for (index, endpoint) in endpoints.into_iter().enumerate() {
+            let stream = YellowstoneGrpcGeyserClient {
+                endpoint: endpoint.url,
+                x_token: endpoint.x_token,
+                ..self.clone()
             };
+            tokio::spawn(stream.run_stream(index, events_tx.clone()));
+        }
+        drop(events_tx);
 
-            loop {
-                tokio::select! {
-                    result = geyser_client.subscribe_with_request(Some(subscribe_request.clone())) => {
-                        match result {
-                            Ok((_subscribe_tx, mut stream)) => {
-                                while let Some(message) = stream.next().await {
-                                    match message {
-                                        Ok(msg) => match msg.update_oneof {
-                                            Some(UpdateOneof::Transaction(transaction_update)) => {
-                                                // Lightweight heartbeat for debugging
-                                                tracing::debug!(target: "geyser", "slot {}", transaction_update.slot);
-
-                                                let _start_time = std::time::Instant::now();
-
-                                                if let Some(transaction_info) =
-                                                    transaction_update.transaction
-                                                {
-                                                    let Ok(signature) =
-                                                        Signature::try_from(transaction_info.signature)
-                                                    else {
-                                                        continue;
-                                                    };
-                                                    let Some(yellowstone_transaction) =
-                                                        transaction_info.transaction
-                                                    else {
-                                                        continue;
-                                                    };
-                                                    let Some(yellowstone_tx_meta) = transaction_info.meta
-                                                    else {
-                                                        continue;
-                                                    };
-                                                    let Ok(versioned_transaction) =
-                                                        create_tx_versioned(yellowstone_transaction)
-                                                    else {
-                                                        continue;
-                                                    };
-                                                    let meta_original = match create_tx_meta(
-                                                        yellowstone_tx_meta,
-                                                    ) {
-                                                        Ok(meta) => meta,
-                                                        Err(err) => {
-                                                            log::error!(
-                                                                "Failed to create transaction meta: {:?}",
-                                                                err
-                                                            );
-                                                            continue;
-                                                        }
-                                                    };
-                                                    // info!("signature {:?}", signature);
-                                                     let  _ = meteora_controller.transaction_handler(
-                                                        signature,
-                                                        versioned_transaction,
-                                                        meta_original,
-                                                        transaction_info.is_vote,
-                                                        transaction_update.slot
-                                                     ).await;
-                                                } else {
-                                                    log::error!("No transaction info in `UpdateOneof::Transaction` at slot {}", transaction_update.slot);
-                                                }
-                                            }
-
-                                            _ => {}
-                                        },
-                                        Err(error) => {
-                                            log::error!("Geyser stream error: {error:?}");
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to subscribe: {:?}", e);
-                            }
+        let mut report =
+            tokio::time::interval(Duration::from_secs(self.config.stats_interval_secs.max(1)));
+        report.tick().await;
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(StreamEvent::Update { endpoint, received_at, received_wall, update }) => {
+                        if let Some(capture) = &self.capture {
+                            capture.record(endpoint, received_wall, &update, &dispatch);
                         }
+                        handle_update(&dispatch, &mut race, endpoint, received_at, update);
+                    }
+                    Some(StreamEvent::Connected(endpoint)) => {
+                        race.set_connected(endpoint, true);
+                        self.live.store(true, Ordering::Relaxed);
+                    }
+                    Some(StreamEvent::Disconnected(endpoint)) => {
+                        race.set_connected(endpoint, false);
+                        self.live.store(race.live() > 0, Ordering::Relaxed);
                     }
+                    None => {
+                        return Err(Error::Custom("all Geyser streams stopped".to_string()))
+                    }
+                },
+                _ = report.tick() => race.report(),
+            }
+        }
+    }
+}
+
+/// Decodes one update and queues it for the handlers; never waits on a handler.
+fn handle_update(
+    dispatch: &DispatchHandle,
+    race: &mut Race,
+    endpoint: usize,
+    received_at: Instant,
+    update: UpdateOneof,
+) {
+    match update {
+        UpdateOneof::Transaction(transaction_update) => {
+            // Lightweight heartbeat for debugging
+            debug!(target: "geyser", "slot {}", transaction_update.slot);
+
+            let Some(transaction_info) = transaction_update.transaction else {
+                log::error!(
+                    "No transaction info in `UpdateOneof::Transaction` at slot {}",
+                    transaction_update.slot
+                );
+                return;
+            };
+            let Ok(signature) = Signature::try_from(transaction_info.signature) else {
+                return;
+            };
+            if !race.arrive(signature, endpoint, received_at) {
+                return;
+            }
+            let Some(yellowstone_transaction) = transaction_info.transaction else {
+                return;
+            };
+            // Fast path: skip the conversions below for transactions no handler can use.
+            if !dispatch.wants(&yellowstone_transaction) {
+                return;
+            }
+            let Some(yellowstone_tx_meta) = transaction_info.meta else {
+                return;
+            };
+            let Ok(versioned_transaction) = create_tx_versioned(yellowstone_transaction) else {
+                return;
+            };
+            let meta_original = match create_tx_meta(yellowstone_tx_meta) {
+                Ok(meta) => meta,
+                Err(err) => {
+                    log::error!("Failed to create transaction meta: {:?}", err);
+                    return;
                 }
+            };
+            dispatch.dispatch(DecodedTransaction {
+                signature,
+                transaction: versioned_transaction,
+                meta: Some(meta_original),
+                is_vote: transaction_info.is_vote,
+                slot: transaction_update.slot,
+            });
+        }
+
+        UpdateOneof::BlockMeta(block_meta) => {
+            if !race.block_meta(block_meta.slot) {
+                return;
             }
-        }).await;
+            dispatch.block_meta(
+                block_meta.slot,
+                &block_meta.blockhash,
+                block_meta.block_height.map(|h| h.block_height),
+            );
+        }
+
+        UpdateOneof::Slot(slot_update) => {
+            if slot_update.status == SlotStatus::SlotConfirmed as i32
+                && race.slot_confirmed(slot_update.slot)
+            {
+                dispatch.slot_confirmed(slot_update.slot);
+            }
+        }
 
-        Ok(())
+        _ => {}
     }
 }
@@ -0,0 +1,159 @@
+use serde_json::json;
+use solana_sdk::signature::Signature;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// Slots remembered per slot-keyed update kind; endpoints are never this many slots apart.
+const SLOT_WINDOW: usize = 256;
+
+/// First arrival of a transaction across all endpoints.
+#[derive(Clone, Copy)]
+struct Arrival {
+    endpoint: usize,
+    at: Instant,
+}
+
+/// Slots already dispatched for one update kind, oldest evicted first.
+#[derive(Default)]
+struct SeenSlots(BTreeSet<u64>);
+
+impl SeenSlots {
+    /// Returns `true` if `slot` was not seen yet. Slots older than the whole window are
+    /// treated as seen.
+    fn insert(&mut self, slot: u64) -> bool {
+        if self.0.len() >= SLOT_WINDOW && self.0.first().is_some_and(|&oldest| slot < oldest) {
+            return false;
+        }
+        if !self.0.insert(slot) {
+            return false;
+        }
+        while self.0.len() > SLOT_WINDOW {
+            self.0.pop_first();
+        }
+        true
+    }
+}
+
+#[derive(Default)]
+struct EndpointStats {
+    connected: bool,
+    /// Transactions delivered by this endpoint, duplicates included.
+    arrivals: u64,
+    /// Transactions this endpoint delivered first.
+    wins: u64,
+    /// Total delay behind the winning endpoint, over `arrivals - wins` late arrivals.
+    lag_ms: f64,
+}
+
+/// Deduplicates transactions, block metas and confirmed slots delivered by several
+/// endpoints (first arrival wins) and keeps per-endpoint transaction arrival statistics.
+pub struct Race {
+    names: Vec<String>,
+    stats: Vec<EndpointStats>,
+    capacity: usize,
+    first: HashMap<Signature, Arrival>,
+    order: VecDeque<Signature>,
+    block_metas: SeenSlots,
+    confirmed_slots: SeenSlots,
+}
+
+impl Race {
+    pub fn new(names: Vec<String>, capacity: usize) -> Self {
+        let stats = names.iter().map(|_| EndpointStats::default()).collect();
+        Self {
+            names,
+            stats,
+            capacity: capacity.max(1),
+            first: HashMap::new(),
+            order: VecDeque::new(),
+            block_metas: SeenSlots::default(),
+            confirmed_slots: SeenSlots::default(),
+        }
+    }
+
+    /// Records an arrival; returns `true` if this is the first one for `signature`.
+    /// Replays by the endpoint that delivered first (after a resumed subscription) are
+    /// not counted.
+    pub fn arrive(&mut self, signature: Signature, endpoint: usize, at: Instant) -> bool {
+        if let Some(first) = self.first.get(&signature) {
+            if first.endpoint != endpoint {
+                let stats = &mut self.stats[endpoint];
+                stats.arrivals += 1;
+                stats.lag_ms += at.saturating_duration_since(first.at).as_secs_f64() * 1000.0;
+            }
+            return false;
+        }
+
+        self.stats[endpoint].arrivals += 1;
+        self.stats[endpoint].wins += 1;
+        self.first.insert(signature, Arrival { endpoint, at });
+        self.order.push_back(signature);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.first.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Returns `true` for the first block meta of `slot` from any endpoint.
+    pub fn block_meta(&mut self, slot: u64) -> bool {
+        self.block_metas.insert(slot)
+    }
+
+    /// Returns `true` the first time any endpoint reports `slot` as confirmed.
+    pub fn slot_confirmed(&mut self, slot: u64) -> bool {
+        self.confirmed_slots.insert(slot)
+    }
+
+    pub fn set_connected(&mut self, endpoint: usize, connected: bool) {
+        if self.stats[endpoint].connected == connected {
+            return;
+        }
+        self.stats[endpoint].connected = connected;
+        let live = self.live();
+        let name = &self.names[endpoint];
+        let total = self.names.len();
+        if connected {
+            info!(target: "geyser", "{name} stream up ({live}/{total} live)");
+        } else if live > 0 {
+            warn!(target: "geyser", "{name} stream down, failing over ({live}/{total} live)");
+        } else {
+            warn!(target: "geyser", "{name} stream down, no Geyser stream left");
+        }
+    }
+
+    /// Number of raced endpoints.
+    pub fn endpoint_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Number of endpoints whose stream currently delivers.
+    pub fn live(&self) -> usize {
+        self.stats.iter().filter(|s| s.connected).count()
+    }
+
+    /// Logs one metrics record per endpoint: who delivers first, and how far behind the rest are.
+    pub fn report(&self) {
+        for (name, stats) in self.names.iter().zip(&self.stats) {
+            let late = stats.arrivals - stats.wins;
+            let avg_lag_ms = if late > 0 {
+                stats.lag_ms / late as f64
+            } else {
+                0.0
+            };
+            info!(
+                target: "metrics",
+                "{}",
+                json!({
+                    "geyser_endpoint": name,
+                    "connected": stats.connected,
+                    "arrivals": stats.arrivals,
+                    "wins": stats.wins,
+                    "avg_lag_ms": avg_lag_ms,
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::config::GeyserConfig;
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Exponential reconnect delay with random jitter, reset once a stream delivers again.
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    jitter: f64,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(config: &GeyserConfig) -> Self {
+        let initial = Duration::from_millis(config.reconnect_initial_ms.max(1));
+        Self {
+            initial,
+            // A cap below the first delay would turn every retry into a busy loop.
+            max: Duration::from_millis(config.reconnect_max_ms).max(initial),
+            jitter: config.reconnect_jitter.clamp(0.0, 1.0),
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt: `initial * 2^attempt`, capped at `max`, then shifted
+    /// by up to ±`jitter` so several bots do not reconnect in lockstep.
+    pub fn next_delay(&mut self) -> Duration {
+        let base = self
+            .initial
+            .saturating_mul(1 << self.attempt.min(16))
+            .min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+        let factor = 1.0 + self.jitter * rand::thread_rng().gen_range(-1.0..=1.0);
+        base.mul_f64(factor)
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
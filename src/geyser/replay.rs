@@ -0,0 +1,145 @@
+use crate::config::GeyserConfig;
+use crate::geyser::capture::{MAGIC, RECORD_HEADER_LEN};
+use crate::geyser::dispatch::DispatchHandle;
+use crate::geyser::handle_update;
+use crate::geyser::race::Race;
+
+use anyhow::{bail, Context};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{info, warn};
+use yellowstone_grpc_proto::geyser::SubscribeUpdate;
+use yellowstone_grpc_proto::prost::Message;
+
+/// One record of a capture file.
+pub struct CapturedUpdate {
+    /// Receive time, microseconds since the Unix epoch.
+    pub received_at_us: u64,
+    pub endpoint: u16,
+    pub update: SubscribeUpdate,
+}
+
+/// Reads the records of a file written by `Capture`, in order.
+pub struct CaptureReader {
+    path: PathBuf,
+    reader: BufReader<File>,
+    failed: bool,
+}
+
+impl CaptureReader {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path).with_context(|| format!("cannot open {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .with_context(|| format!("{} is too short", path.display()))?;
+        if &magic != MAGIC {
+            bail!("{} is not a capture file", path.display());
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            reader,
+            failed: false,
+        })
+    }
+
+    fn read_record(&mut self) -> anyhow::Result<Option<CapturedUpdate>> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        self.reader
+            .read_exact(&mut header)
+            .context("truncated record header")?;
+        let len = u32::from_le_bytes(header[0..4].try_into()?) as usize;
+        let received_at_us = u64::from_le_bytes(header[4..12].try_into()?);
+        let endpoint = u16::from_le_bytes(header[12..14].try_into()?);
+        let mut payload = vec![0u8; len];
+        self.reader
+            .read_exact(&mut payload)
+            .context("truncated record payload")?;
+        let update = SubscribeUpdate::decode(payload.as_slice()).context("undecodable update")?;
+        Ok(Some(CapturedUpdate {
+            received_at_us,
+            endpoint,
+            update,
+        }))
+    }
+}
+
+impl Iterator for CaptureReader {
+    type Item = anyhow::Result<CapturedUpdate>;
+
+    /// Stops after the first error; a file cut off mid-record ends with one.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.read_record() {
+            Ok(record) => record.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e.context(format!("in {}", self.path.display()))))
+            }
+        }
+    }
+}
+
+/// Feeds captured updates through the live pipeline (endpoint race, raw prefilter,
+/// decoding, handler queues), in file order. With `speed` > 0 the original gaps between
+/// updates are kept, divided by `speed`; 0 replays as fast as possible. Returns the
+/// number of updates replayed.
+pub async fn replay(
+    paths: &[PathBuf],
+    speed: f64,
+    config: &GeyserConfig,
+    dispatch: &DispatchHandle,
+) -> anyhow::Result<u64> {
+    // Endpoint indices in the capture refer to the config it was recorded with.
+    let names: Vec<String> = if config.endpoints.is_empty() {
+        vec!["geyser".to_string()]
+    } else {
+        config.endpoints.iter().map(|e| e.name.clone()).collect()
+    };
+    let mut race = Race::new(names, config.dedup_capacity);
+    let mut first_at_us = None;
+    let started = Instant::now();
+    let mut replayed = 0u64;
+
+    for path in paths {
+        info!("Replaying {}", path.display());
+        for record in CaptureReader::open(path)? {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    warn!("{:#}", e);
+                    break;
+                }
+            };
+            let endpoint = record.endpoint as usize;
+            if endpoint >= race.endpoint_count() {
+                warn!("record from unknown endpoint {} skipped", endpoint);
+                continue;
+            }
+            let Some(update) = record.update.update_oneof else {
+                continue;
+            };
+            let first_at_us = *first_at_us.get_or_insert(record.received_at_us);
+            let offset_us = record.received_at_us.saturating_sub(first_at_us) as f64;
+            // Arrival times keep the original gaps, so the race statistics match the capture.
+            let received_at = started + Duration::from_secs_f64(offset_us / 1_000_000.0);
+            if speed > 0.0 {
+                let offset = Duration::from_secs_f64(offset_us / 1_000_000.0 / speed);
+                tokio::time::sleep_until(started + offset).await;
+            }
+            handle_update(dispatch, &mut race, endpoint, received_at, update);
+            replayed += 1;
+        }
+    }
+    race.report();
+    Ok(replayed)
+}
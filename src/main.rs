@@ -4,13 +4,18 @@ use dotenv::dotenv;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
     bench::Bench,
-    config::PingThingsArgs,
-    geyser::{GeyserResult, YellowstoneGrpcGeyser, YellowstoneGrpcGeyserClient},
+    config::{PingThingsArgs, WsolMode},
+    geyser::{
+        capture::Capture, dispatch::Dispatcher, entries::EntrySource, logs::LogsSource,
+        GeyserResult, YellowstoneGrpcGeyser, YellowstoneGrpcGeyserClient,
+    },
     meteora::controller::MeteoraController,
+    tx_senders::connection::ConnectionManager,
+    wsol::WsolMaintainer,
 };
 
 mod bench;
@@ -19,6 +24,7 @@ mod core;
 mod geyser;
 mod meteora;
 mod tx_senders;
+mod wsol;
 
 #[tokio::main]
 async fn main() -> GeyserResult<()> {
@@ -31,6 +37,30 @@ async fn main() -> GeyserResult<()> {
     // Parse CLI/config arguments
     let config: PingThingsArgs = PingThingsArgs::new();
     let bench = Bench::new(config.clone());
+    if let Some(oracle) = &bench.fee_oracle {
+        oracle.spawn_rpc_sampler();
+    }
+    if config.compute_budget.auto_calibrate {
+        let bench = bench.clone();
+        tokio::spawn(async move { bench.calibrate_compute_budget().await });
+    }
+    if config.wsol.mode == WsolMode::Persistent {
+        if config.simulate {
+            info!("Persistent WSOL maintenance disabled in simulation mode");
+        } else {
+            let maintainer = WsolMaintainer::new(
+                config.wsol.clone(),
+                config.http_rpc.clone(),
+                &bench.tx_config,
+                bench.blockhash.clone(),
+                bench.snipes_in_flight.clone(),
+            );
+            tokio::spawn(maintainer.run());
+        }
+    }
+    if config.blockhash.policy.uses_rpc() {
+        bench.blockhash.spawn_rpc_poller(config.http_rpc.clone());
+    }
     let meteora = MeteoraController::new(config.clone(), bench.clone());
 
     info!("Starting with config: {:?}", config);
@@ -41,21 +71,9 @@ async fn main() -> GeyserResult<()> {
         yellowstone_grpc_proto::geyser::SubscribeRequestFilterAccounts,
     > = HashMap::new();
 
-    let mut tx_filters: HashMap<
-        String,
-        yellowstone_grpc_proto::geyser::SubscribeRequestFilterTransactions,
-    > = HashMap::new();
-    tx_filters.insert(
-        "meteora_transaction_filter".to_string(),
-        yellowstone_grpc_proto::geyser::SubscribeRequestFilterTransactions {
-            vote: Some(false),
-            failed: Some(false),
-            account_include: vec![crate::meteora::constants::METEORA_PROGRAM_ID.to_string()],
-            account_exclude: vec![],
-            account_required: vec![],
-            signature: None,
-        },
-    );
+    // Every handler of the decoded stream contributes its own transaction filter
+    let dispatcher = Dispatcher::new().with_handler(meteora);
+    let tx_filters = dispatcher.subscribe_filters();
 
     // Determine optional X-Token header
     let x_token = if config.geyser_x_token.trim().is_empty() {
@@ -72,9 +90,70 @@ async fn main() -> GeyserResult<()> {
         account_filters,
         tx_filters,
         Arc::new(RwLock::new(Default::default())),
+    )
+    .with_config(config.geyser.clone());
+    let geyser_client = if config.blockhash.policy.uses_geyser() {
+        geyser_client.with_blockhash_tracking()
+    } else {
+        geyser_client
+    };
+    let geyser_client = if config.capture.enabled {
+        match Capture::spawn(config.capture.clone()) {
+            Ok(capture) => geyser_client.with_capture(capture),
+            Err(e) => {
+                warn!("stream capture disabled: {:#}", e);
+                geyser_client
+            }
+        }
+    } else {
+        geyser_client
+    };
+
+    // Connect every sender before going live, then keep the connections warm
+    let connections = ConnectionManager::new(&bench.rpcs, &config);
+    let ready = connections.warm_up().await;
+    connections.spawn_keepalive();
+    info!(
+        "{}/{} senders ready, going live",
+        ready,
+        connections.sender_count()
     );
 
-    // Start consuming updates
-    geyser_client.consume(meteora).await?;
-    Ok(())
+    // Start the handler workers, then consume updates
+    let dispatch = dispatcher.spawn(&config.dispatch);
+    if config.entries.enabled {
+        match EntrySource::new(config.entries.clone()) {
+            Ok(entries) => {
+                tokio::spawn(entries.run(dispatch.clone()));
+            }
+            Err(e) => warn!("entry source disabled: {:#}", e),
+        }
+    }
+    let logs = LogsSource::new(
+        config.logs_fallback.clone(),
+        config.http_rpc.clone(),
+        config.ws_rpc.clone(),
+    );
+    let run = async {
+        if config.geyser_url.trim().is_empty() && config.geyser.endpoints.is_empty() {
+            info!("No Geyser endpoint configured, detecting pools through logsSubscribe");
+            logs.run(dispatch).await;
+            return Ok(());
+        }
+        if config.logs_fallback.enabled {
+            let logs = logs.fallback_for(geyser_client.liveness());
+            tokio::spawn(logs.run(dispatch.clone()));
+        }
+        geyser_client.consume(dispatch).await
+    };
+    let result = tokio::select! {
+        result = run => result,
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutting down");
+            Ok(())
+        }
+    };
+    // Statistics changed since the last periodic save would be lost otherwise.
+    bench.scores.flush();
+    result
 }
@@ -1,5 +1,10 @@
+use solana_sdk::pubkey::Pubkey;
+
 // Program ID for Meteora Dynamic AMM pools program.
 pub const METEORA_PROGRAM_ID: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
+/// `METEORA_PROGRAM_ID`, parsed at compile time for the hot path.
+pub const METEORA_PROGRAM: Pubkey =
+    solana_sdk::pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
 
 // Wrapped SOL mint address on Solana mainnet.
 pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
@@ -35,27 +40,18 @@ pub mod init_pool_indices {
 
 /// Hard-coded account indices for the `swap` instruction.
 pub mod swap_indices {
-    #[allow(dead_code)]
     pub const POOL: usize = 0;
     #[allow(dead_code)]
     pub const USER_SOURCE_TOKEN: usize = 1;
     #[allow(dead_code)]
     pub const USER_DEST_TOKEN: usize = 2;
-    #[allow(dead_code)]
     pub const A_VAULT: usize = 3;
-    #[allow(dead_code)]
     pub const B_VAULT: usize = 4;
-    #[allow(dead_code)]
     pub const A_TOKEN_VAULT: usize = 5;
-    #[allow(dead_code)]
     pub const B_TOKEN_VAULT: usize = 6;
-    #[allow(dead_code)]
     pub const A_VAULT_LP_MINT: usize = 7;
-    #[allow(dead_code)]
     pub const B_VAULT_LP_MINT: usize = 8;
-    #[allow(dead_code)]
     pub const A_VAULT_LP: usize = 9;
-    #[allow(dead_code)]
     pub const B_VAULT_LP: usize = 10;
     #[allow(dead_code)]
     pub const PROTOCOL_TOKEN_FEE: usize = 11;
@@ -72,4 +68,7 @@ pub mod swap_indices {
 pub const INIT_POOL_DISCRIM: [u8; 8] = [48, 149, 220, 130, 61, 11, 9, 178]; // Hex: [0x30, 0x95, 0xdc, 0x82, 0x3d, 0x0b, 0x09, 0xb2]
 /// Discriminator for `initializePermissionlessConstantProductPoolWithConfig` (v1)
 pub const INIT_POOL_DISCRIM_V1: [u8; 8] = [0x22, 0x80, 0x79, 0x2d, 0xab, 0x3e, 0xd2, 0x7e];
+/// Anchor log line of both init-pool instructions (`...WithConfig` and `...WithConfig2`).
+pub const INIT_POOL_LOG_PREFIX: &str =
+    "Program log: Instruction: InitializePermissionlessConstantProductPoolWithConfig";
 pub const SWAP_DISCRIM: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
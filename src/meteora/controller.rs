@@ -1,11 +1,17 @@
 use crate::bench::Bench;
 use crate::config::PingThingsArgs;
 use crate::core::extract_instructions;
-use crate::meteora::constants::{init_pool_indices as idx, METEORA_PROGRAM_ID, WSOL_MINT};
-use crate::meteora::types::{MeteoraSwapParams, TradeDirection};
+use crate::geyser::handler::{
+    DecodedTransaction, PriorityCheck, RawCheck, TransactionFilter, TransactionHandler,
+};
+use crate::meteora::constants::{
+    init_pool_indices as idx, METEORA_PROGRAM, METEORA_PROGRAM_ID, WSOL_MINT,
+};
+use crate::meteora::types::{MeteoraSwapParams, PoolVariant, TradeDirection};
 
-use crate::meteora::constants::INIT_POOL_DISCRIM;
-use solana_sdk::hash::Hash;
+use crate::meteora::constants::{INIT_POOL_DISCRIM, INIT_POOL_DISCRIM_V1};
+use async_trait::async_trait;
+use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signature::Signer;
@@ -13,6 +19,7 @@ use solana_sdk::transaction::VersionedTransaction;
 use solana_transaction_status::TransactionStatusMeta;
 use std::str::FromStr;
 use tracing::debug;
+use yellowstone_grpc_proto::solana::storage::confirmed_block::Transaction as RawTransaction;
 
 /// Controller that listens to Meteora pool initialization and triggers a buy once WSOL liquidity appears.
 pub struct MeteoraController {
@@ -34,12 +41,24 @@ impl MeteoraController {
         }
     }
 
+    /// Handles block-meta updates, used to keep the blockhash cache fresh.
+    pub fn block_meta_handler(&self, slot: u64, blockhash: &str, block_height: Option<u64>) {
+        self.bench
+            .blockhash
+            .on_block_meta(slot, blockhash, block_height);
+    }
+
+    /// Handles a slot reaching `confirmed` commitment.
+    pub fn slot_confirmed_handler(&self, slot: u64) {
+        self.bench.blockhash.on_slot_confirmed(slot);
+    }
+
     /// Handles every transaction pushed from Yellowstone Geyser.
     pub async fn transaction_handler(
         &mut self,
         _signature: solana_sdk::signature::Signature,
-        transaction: VersionedTransaction,
-        meta: TransactionStatusMeta,
+        transaction: &VersionedTransaction,
+        meta: Option<&TransactionStatusMeta>,
         _is_vote: bool,
         _slot: u64,
     ) -> anyhow::Result<()> {
@@ -48,46 +67,42 @@ impl MeteoraController {
             _signature
         );
 
+        self.bench.observe_transaction(transaction);
+
         if self.is_buy {
             debug!("[LOG_HANDLER] Already bought, exiting handler.");
             // Already bought, ignore further processing.
             return Ok(());
         }
 
-        let instructions = extract_instructions(meta.clone(), transaction.clone())?;
+        let no_meta;
+        let meta = match meta {
+            Some(meta) => meta,
+            // Seen before execution: accounts from lookup tables are unknown, so leave the
+            // transaction to the executed copy.
+            None if transaction
+                .message
+                .address_table_lookups()
+                .is_some_and(|lookups| !lookups.is_empty()) =>
+            {
+                debug!("[LOG_HANDLER] No meta and lookup tables used, exiting handler.");
+                return Ok(());
+            }
+            None => {
+                no_meta = TransactionStatusMeta::default();
+                &no_meta
+            }
+        };
+        let instructions = extract_instructions(meta, transaction)?;
         debug!(
             "[LOG_HANDLER] Extracted {} instructions.",
             instructions.len()
         );
 
-        let program_id = Pubkey::from_str(METEORA_PROGRAM_ID)?;
-        let target_instruction_opt = instructions.iter().find(|inst| {
-            if inst.program_id != program_id {
-                return false;
-            }
-            // Log details for instructions from the Meteora program
-            debug!("[LOG_HANDLER_FIND] Checking Meteora instruction. Accounts: {}, Data len: {}, Program ID: {}", inst.accounts.len(), inst.data.len(), inst.program_id);
-            if inst.data.len() >= 8 {
-                debug!("[LOG_HANDLER_FIND] Data prefix (first 8 bytes): {:?}", &inst.data[0..8]);
-            } else {
-                debug!("[LOG_HANDLER_FIND] Data (less than 8 bytes): {:?}", &inst.data);
-            }
-            
-            let data = &inst.data;
-            let matches_v2 = data.starts_with(&INIT_POOL_DISCRIM);
-            // Assuming INIT_POOL_DISCRIM_V1 is correctly defined in constants
-            let matches_v1 = data.starts_with(&crate::meteora::constants::INIT_POOL_DISCRIM_V1); 
-            
-            if matches_v1 || matches_v2 {
-                debug!("[LOG_HANDLER_FIND] Found a match! V1: {}, V2: {}", matches_v1, matches_v2);
-            }
-
-            matches_v1 || matches_v2
-        });
-        let target_instruction = match target_instruction_opt {
-            Some(i) => {
+        let (target_instruction, variant) = match find_init_pool_instruction(&instructions) {
+            Some(found) => {
                 debug!("[LOG_HANDLER] Found target Meteora instruction.");
-                i
+                found
             }
             None => {
                 debug!("[LOG_HANDLER] Target Meteora instruction not found, exiting handler.");
@@ -95,32 +110,15 @@ impl MeteoraController {
             }
         };
 
-        // Pull token mints from account list.
-        let token_a_mint = target_instruction.accounts[idx::TOKEN_A_MINT].pubkey;
-        let token_b_mint = target_instruction.accounts[idx::TOKEN_B_MINT].pubkey;
-
-        debug!(
-            "[LOG_HANDLER] Checking token pair for WSOL. Token A: {}, Token B: {}",
-            token_a_mint, token_b_mint
-        );
-        // React only when WSOL is one of the pair.
-        let (other_token_mint, direction, protocol_fee_acc) =
-            if token_a_mint == Pubkey::from_str(WSOL_MINT)? {
-                (
-                    token_b_mint,
-                    TradeDirection::AtoB,
-                    target_instruction.accounts[idx::PROTOCOL_TOKEN_A_FEE].pubkey,
-                )
-            } else if token_b_mint == Pubkey::from_str(WSOL_MINT)? {
-                (
-                    token_a_mint,
-                    TradeDirection::BtoA,
-                    target_instruction.accounts[idx::PROTOCOL_TOKEN_B_FEE].pubkey,
-                )
-            } else {
-                debug!("[LOG_HANDLER] Not a WSOL pool, exiting handler.");
-                return Ok(()); // Not a WSOL pool.
-            };
+        // Construct swap params; `None` means the pair has no WSOL side.
+        let owner_keypair = Keypair::from_base58_string(&self.config.private_key);
+        let Some(params) =
+            swap_params_from_init(target_instruction, variant, &owner_keypair.pubkey())
+        else {
+            debug!("[LOG_HANDLER] Not a WSOL pool, exiting handler.");
+            return Ok(());
+        };
+        let other_token_mint = params.mint_target_token;
 
         debug!(
             "[LOG_HANDLER] Checking if mint {} was seen before...",
@@ -136,48 +134,195 @@ impl MeteoraController {
         }
 
         debug!(
-            "Detected first WSOL liquidity for mint {} in pool {}",
-            other_token_mint,
-            target_instruction.accounts[idx::POOL].pubkey
-        );
-
-        // Construct swap params.
-        let owner_keypair = Keypair::from_base58_string(&self.config.private_key);
-        let owner = owner_keypair.pubkey();
-        let user_source = spl_associated_token_account::get_associated_token_address(
-            &owner,
-            &Pubkey::from_str(WSOL_MINT)?,
+            "Detected first WSOL liquidity for mint {} in pool {} ({:?})",
+            other_token_mint, params.pool, variant
         );
-        let user_destination =
-            spl_associated_token_account::get_associated_token_address(&owner, &other_token_mint);
-
-        let params = MeteoraSwapParams {
-            pool: target_instruction.accounts[idx::POOL].pubkey,
-            direction,
-            user_source,
-            user_destination,
-            a_vault: target_instruction.accounts[idx::A_VAULT].pubkey,
-            b_vault: target_instruction.accounts[idx::B_VAULT].pubkey,
-            a_token_vault: target_instruction.accounts[idx::A_TOKEN_VAULT].pubkey,
-            b_token_vault: target_instruction.accounts[idx::B_TOKEN_VAULT].pubkey,
-            a_vault_lp_mint: target_instruction.accounts[idx::A_VAULT_LP_MINT].pubkey,
-            b_vault_lp_mint: target_instruction.accounts[idx::B_VAULT_LP_MINT].pubkey,
-            a_vault_lp: target_instruction.accounts[idx::A_VAULT_LP].pubkey,
-            b_vault_lp: target_instruction.accounts[idx::B_VAULT_LP].pubkey,
-            protocol_token_fee: protocol_fee_acc,
-            vault_program: target_instruction.accounts[idx::VAULT_PROGRAM].pubkey,
-            token_program: target_instruction.accounts[idx::TOKEN_PROGRAM].pubkey,
-            mint_target_token: other_token_mint,
-        };
 
-        let recent_blockhash: Hash = *transaction.message.recent_blockhash();
+        let (recent_blockhash, last_valid_block_height) = self
+            .bench
+            .blockhash
+            .resolve(*transaction.message.recent_blockhash());
         self.is_buy = true;
 
         self.bench
             .clone()
-            .send_buy_tx_meteora(recent_blockhash, params)
+            .send_buy_tx_meteora(recent_blockhash, last_valid_block_height, params)
             .await;
 
         Ok(())
     }
 }
+
+#[async_trait]
+impl TransactionHandler for MeteoraController {
+    fn name(&self) -> &str {
+        "meteora"
+    }
+
+    fn filter(&self) -> TransactionFilter {
+        TransactionFilter {
+            account_include: vec![Pubkey::from_str(METEORA_PROGRAM_ID).unwrap()],
+            ..Default::default()
+        }
+    }
+
+    /// Pool initializations are what the sniper exists for; never drop them.
+    fn priority(&self) -> Option<PriorityCheck> {
+        Some(is_init_pool_transaction)
+    }
+
+    fn raw_prefilter(&self) -> Option<RawCheck> {
+        let fees = &self.config.priority_fee;
+        // Fee observation samples every Meteora transaction, so all of them are decoded.
+        if fees.enabled && fees.observe_geyser && fees.observation_window > 0 {
+            return None;
+        }
+        Some(raw_invokes_init_pool)
+    }
+
+    async fn handle_transaction(&mut self, transaction: &DecodedTransaction) -> anyhow::Result<()> {
+        self.transaction_handler(
+            transaction.signature,
+            &transaction.transaction,
+            transaction.meta.as_ref(),
+            transaction.is_vote,
+            transaction.slot,
+        )
+        .await
+    }
+
+    fn on_block_meta(&mut self, slot: u64, blockhash: &str, block_height: Option<u64>) {
+        self.block_meta_handler(slot, blockhash, block_height);
+    }
+
+    fn on_slot_confirmed(&mut self, slot: u64) {
+        self.slot_confirmed_handler(slot);
+    }
+}
+
+fn is_init_pool_transaction(transaction: &DecodedTransaction) -> bool {
+    invokes_init_pool(&transaction.transaction)
+}
+
+/// Whether a top-level instruction of the transaction initializes a Meteora pool. Only
+/// looks at static keys, which always hold invoked program IDs, so it works without meta.
+pub fn invokes_init_pool(transaction: &VersionedTransaction) -> bool {
+    let message = &transaction.message;
+    let keys = message.static_account_keys();
+    message.instructions().iter().any(|inst| {
+        keys.get(inst.program_id_index as usize) == Some(&METEORA_PROGRAM)
+            && is_init_pool_data(&inst.data)
+    })
+}
+
+/// `invokes_init_pool` on the prost transaction tonic already decoded, before the
+/// conversion to Solana types.
+pub fn raw_invokes_init_pool(transaction: &RawTransaction) -> bool {
+    let Some(message) = &transaction.message else {
+        return false;
+    };
+    message.instructions.iter().any(|inst| {
+        message
+            .account_keys
+            .get(inst.program_id_index as usize)
+            .is_some_and(|key| key.as_slice() == METEORA_PROGRAM.as_ref())
+            && is_init_pool_data(&inst.data)
+    })
+}
+
+fn is_init_pool_data(data: &[u8]) -> bool {
+    data.starts_with(&INIT_POOL_DISCRIM) || data.starts_with(&INIT_POOL_DISCRIM_V1)
+}
+
+/// Finds the pool-initialization instruction and the variant that created the pool.
+pub fn find_init_pool_instruction(
+    instructions: &[Instruction],
+) -> Option<(&Instruction, PoolVariant)> {
+    let program_id = Pubkey::from_str(METEORA_PROGRAM_ID).ok()?;
+    instructions.iter().find_map(|inst| {
+        if inst.program_id != program_id {
+            return None;
+        }
+        // Log details for instructions from the Meteora program
+        debug!("[LOG_HANDLER_FIND] Checking Meteora instruction. Accounts: {}, Data len: {}, Program ID: {}", inst.accounts.len(), inst.data.len(), inst.program_id);
+        if inst.data.len() >= 8 {
+            debug!("[LOG_HANDLER_FIND] Data prefix (first 8 bytes): {:?}", &inst.data[0..8]);
+        } else {
+            debug!("[LOG_HANDLER_FIND] Data (less than 8 bytes): {:?}", &inst.data);
+        }
+
+        let data = &inst.data;
+        let variant = if data.starts_with(&INIT_POOL_DISCRIM) {
+            PoolVariant::ConfigV2
+        } else if data.starts_with(&INIT_POOL_DISCRIM_V1) {
+            PoolVariant::ConfigV1
+        } else {
+            return None;
+        };
+        debug!("[LOG_HANDLER_FIND] Found a match! {:?}", variant);
+        Some((inst, variant))
+    })
+}
+
+/// Derives the swap accounts for buying the non-WSOL side of a freshly initialized pool.
+/// Returns `None` when neither mint is WSOL or the account list is too short.
+pub fn swap_params_from_init(
+    init_instruction: &Instruction,
+    variant: PoolVariant,
+    owner: &Pubkey,
+) -> Option<MeteoraSwapParams> {
+    let accounts = &init_instruction.accounts;
+    if accounts.len() <= idx::TOKEN_PROGRAM {
+        return None;
+    }
+
+    // Pull token mints from account list.
+    let token_a_mint = accounts[idx::TOKEN_A_MINT].pubkey;
+    let token_b_mint = accounts[idx::TOKEN_B_MINT].pubkey;
+    let wsol_mint = Pubkey::from_str(WSOL_MINT).ok()?;
+
+    debug!(
+        "[LOG_HANDLER] Checking token pair for WSOL. Token A: {}, Token B: {}",
+        token_a_mint, token_b_mint
+    );
+    // React only when WSOL is one of the pair.
+    let (other_token_mint, direction, protocol_fee_acc) = if token_a_mint == wsol_mint {
+        (
+            token_b_mint,
+            TradeDirection::AtoB,
+            accounts[idx::PROTOCOL_TOKEN_A_FEE].pubkey,
+        )
+    } else if token_b_mint == wsol_mint {
+        (
+            token_a_mint,
+            TradeDirection::BtoA,
+            accounts[idx::PROTOCOL_TOKEN_B_FEE].pubkey,
+        )
+    } else {
+        return None;
+    };
+
+    let user_source = spl_associated_token_account::get_associated_token_address(owner, &wsol_mint);
+    let user_destination =
+        spl_associated_token_account::get_associated_token_address(owner, &other_token_mint);
+
+    Some(MeteoraSwapParams {
+        pool: accounts[idx::POOL].pubkey,
+        variant,
+        direction,
+        user_source,
+        user_destination,
+        a_vault: accounts[idx::A_VAULT].pubkey,
+        b_vault: accounts[idx::B_VAULT].pubkey,
+        a_token_vault: accounts[idx::A_TOKEN_VAULT].pubkey,
+        b_token_vault: accounts[idx::B_TOKEN_VAULT].pubkey,
+        a_vault_lp_mint: accounts[idx::A_VAULT_LP_MINT].pubkey,
+        b_vault_lp_mint: accounts[idx::B_VAULT_LP_MINT].pubkey,
+        a_vault_lp: accounts[idx::A_VAULT_LP].pubkey,
+        b_vault_lp: accounts[idx::B_VAULT_LP].pubkey,
+        protocol_token_fee: protocol_fee_acc,
+        vault_program: accounts[idx::VAULT_PROGRAM].pubkey,
+        token_program: accounts[idx::TOKEN_PROGRAM].pubkey,
+        mint_target_token: other_token_mint,
+    })
+}
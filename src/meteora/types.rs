@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Direction of the swap: A to B or B to A.
@@ -7,10 +8,21 @@ pub enum TradeDirection {
     BtoA,
 }
 
+/// Init instruction that created the pool; swap cost is calibrated per variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolVariant {
+    /// `initializePermissionlessConstantProductPoolWithConfig`
+    ConfigV1,
+    /// `initializePermissionlessConstantProductPoolWithConfig2`
+    ConfigV2,
+}
+
 /// All accounts required to build a Meteora swap instruction.
 #[derive(Debug, Clone)]
 pub struct MeteoraSwapParams {
     pub pool: Pubkey,
+    pub variant: PoolVariant,
     pub direction: TradeDirection,
 
     // User token accounts
@@ -40,3 +52,20 @@ pub struct MeteoraSwapParams {
 
     pub mint_target_token: Pubkey,
 }
+
+impl MeteoraSwapParams {
+    /// Pool-side accounts the swap write-locks; used to sample contention fees.
+    pub fn writable_pool_accounts(&self) -> Vec<Pubkey> {
+        vec![
+            self.pool,
+            self.a_vault,
+            self.b_vault,
+            self.a_token_vault,
+            self.b_token_vault,
+            self.a_vault_lp_mint,
+            self.b_vault_lp_mint,
+            self.a_vault_lp,
+            self.b_vault_lp,
+        ]
+    }
+}
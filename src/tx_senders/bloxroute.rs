@@ -1,5 +1,6 @@
 use crate::config::RpcType;
-use crate::tx_senders::transaction::{build_meteora_swap_tx, TransactionConfig};
+use crate::tx_senders::connection::ping_http;
+use crate::tx_senders::error::SendError;
 use crate::tx_senders::{TxResult, TxSender};
 
 use anyhow::Context;
@@ -10,8 +11,8 @@ use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::hash::Hash;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::info;
@@ -22,7 +23,6 @@ pub struct BloxrouteTxSender {
     name: String,
     auth_header: Option<String>,
     client: Client,
-    tx_config: TransactionConfig,
     rpc_client: Arc<RpcClient>,
 }
 
@@ -31,7 +31,6 @@ impl BloxrouteTxSender {
         name: String,
         url: String,
         auth_header: Option<String>,
-        tx_config: TransactionConfig,
         client: Client,
         rpc_client: Arc<RpcClient>,
     ) -> Self {
@@ -40,7 +39,6 @@ impl BloxrouteTxSender {
             name,
             auth_header,
             client,
-            tx_config,
             rpc_client,
         }
     }
@@ -57,22 +55,14 @@ impl TxSender for BloxrouteTxSender {
         self.name.clone()
     }
 
-    async fn send_meteora_swap(
-        &self,
-        params: &crate::meteora::types::MeteoraSwapParams,
-        recent_blockhash: Hash,
-    ) -> anyhow::Result<TxResult> {
-        // Build VersionedTransaction
-        let tx = build_meteora_swap_tx(
-            &self.tx_config,
-            &RpcType::SolanaRpc,
-            recent_blockhash,
-            params,
-        );
+    fn rpc_type(&self) -> RpcType {
+        RpcType::Bloxroute
+    }
 
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
         // Serialize to raw bytes and then base64
         let cfg = config::standard();
-        let tx_bytes = bincode_serde::encode_to_vec(&tx, cfg).context("cannot serialize tx")?;
+        let tx_bytes = bincode_serde::encode_to_vec(tx, cfg).context("cannot serialize tx")?;
         let tx_base64 = BASE64_STD.encode(tx_bytes);
 
         // Prepare JSON body and serialize manually
@@ -92,11 +82,17 @@ impl TxSender for BloxrouteTxSender {
             req = req.header("Authorization", auth);
         }
 
-        let resp = req.send().await?;
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
         let status = resp.status();
-        let text = resp.text().await?;
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
         if !status.is_success() {
-            return Err(anyhow::anyhow!("bloxroute submit failed: {}", text));
+            return Err(SendError::from_bloxroute(&self.name, status, &text).into());
         }
 
         // Try parse json {"signature":"..."}
@@ -114,4 +110,8 @@ impl TxSender for BloxrouteTxSender {
     async fn get_block_height(&self) -> anyhow::Result<u64> {
         Ok(self.rpc_client.get_block_height().await?)
     }
+
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        ping_http(&self.name, &self.client, &self.url).await
+    }
 }
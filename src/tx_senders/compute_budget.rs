@@ -0,0 +1,175 @@
+use crate::config::{PingThingsArgs, RpcType};
+use crate::core::blockhash::BlockhashCache;
+use crate::core::{extract_instructions, fetch_transaction};
+use crate::meteora::controller::{find_init_pool_instruction, swap_params_from_init};
+use crate::meteora::types::PoolVariant;
+use crate::tx_senders::transaction::{build_meteora_swap_tx, TransactionConfig};
+
+use anyhow::Context;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+/// Maximum compute units a transaction may request.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Maximum (and default) loaded-accounts data size, 64 MiB.
+const MAX_LOADED_ACCOUNTS_DATA_SIZE: u32 = 64 * 1024 * 1024;
+/// Per-account overhead the runtime adds to the loaded data size.
+const TRANSACTION_ACCOUNT_BASE_SIZE: u64 = 64;
+
+/// Measured budget for one pool variant, safety margin included.
+#[derive(Debug, Clone, Copy)]
+pub struct CalibratedBudget {
+    pub compute_unit_limit: u32,
+    pub loaded_accounts_data_size: u32,
+}
+
+/// Calibrated budgets keyed by pool variant, shared between calibration and the hot path.
+#[derive(Default)]
+pub struct ComputeBudgetTable {
+    budgets: RwLock<HashMap<PoolVariant, CalibratedBudget>>,
+}
+
+impl ComputeBudgetTable {
+    pub fn get(&self, variant: PoolVariant) -> Option<CalibratedBudget> {
+        self.budgets.read().unwrap().get(&variant).copied()
+    }
+
+    pub fn insert(&self, variant: PoolVariant, budget: CalibratedBudget) {
+        self.budgets.write().unwrap().insert(variant, budget);
+    }
+}
+
+/// Simulates the swap template of every configured pool variant and records the results.
+/// Variants that fail to calibrate keep the static `compute_unit_limit`.
+pub async fn calibrate(
+    config: &PingThingsArgs,
+    tx_config: &TransactionConfig,
+    table: &ComputeBudgetTable,
+    blockhash: &BlockhashCache,
+) {
+    let rpc = RpcClient::new(config.http_rpc.clone());
+    let margin = config.compute_budget.safety_margin;
+
+    for (variant, signature) in &config.compute_budget.calibration_txs {
+        match calibrate_variant(&rpc, blockhash, tx_config, *variant, signature, margin).await {
+            Ok(budget) => {
+                info!(
+                    "Calibrated {:?}: CU limit {}, loaded accounts data size {} bytes",
+                    variant, budget.compute_unit_limit, budget.loaded_accounts_data_size
+                );
+                table.insert(*variant, budget);
+            }
+            Err(e) => warn!(
+                "compute budget calibration for {:?} failed: {:#}",
+                variant, e
+            ),
+        }
+    }
+}
+
+async fn calibrate_variant(
+    rpc: &RpcClient,
+    blockhash_cache: &BlockhashCache,
+    tx_config: &TransactionConfig,
+    variant: PoolVariant,
+    signature: &str,
+    margin: f64,
+) -> anyhow::Result<CalibratedBudget> {
+    let signature = Signature::from_str(signature).context("invalid calibration signature")?;
+    let (transaction, meta) = fetch_transaction(rpc, &signature).await?;
+    let instructions = extract_instructions(&meta, &transaction)?;
+
+    let (init_instruction, found_variant) = find_init_pool_instruction(&instructions)
+        .context("calibration transaction has no init-pool instruction")?;
+    if found_variant != variant {
+        warn!(
+            "calibration tx {} initializes a {:?} pool, configured as {:?}",
+            signature, found_variant, variant
+        );
+    }
+    let params = swap_params_from_init(init_instruction, variant, &tx_config.keypair.pubkey())
+        .context("calibration pool has no WSOL side")?;
+
+    // Probe with the maximum budget so the measurement is not capped by the current limit.
+    let mut probe = tx_config.clone();
+    probe.compute_unit_limit = MAX_COMPUTE_UNIT_LIMIT;
+    probe.compute_unit_price = 0;
+    probe.loaded_accounts_data_size_limit = 0;
+
+    let blockhash = blockhash_cache.get_or_fetch(rpc).await?;
+    let tx = build_meteora_swap_tx(&probe, &RpcType::SolanaRpc, blockhash, &params);
+
+    let sim_cfg = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(CommitmentConfig::processed()),
+        encoding: Some(UiTransactionEncoding::Base64),
+        accounts: None,
+        min_context_slot: None,
+        inner_instructions: false,
+    };
+    let sim = rpc
+        .simulate_transaction_with_config(&tx, sim_cfg)
+        .await
+        .context("simulation RPC failed")?
+        .value;
+    if let Some(err) = sim.err {
+        anyhow::bail!("swap template simulation failed: {:?}", err);
+    }
+    let units = sim
+        .units_consumed
+        .context("simulation did not report consumed units")?;
+
+    let loaded_size = loaded_accounts_data_size(rpc, tx.message.static_account_keys()).await?;
+
+    Ok(CalibratedBudget {
+        compute_unit_limit: with_margin(units, margin).min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32,
+        loaded_accounts_data_size: with_margin(loaded_size, margin)
+            .min(MAX_LOADED_ACCOUNTS_DATA_SIZE as u64) as u32,
+    })
+}
+
+/// Approximates the runtime's loaded-accounts accounting: account data plus a fixed
+/// overhead per account, including program data of upgradeable programs.
+async fn loaded_accounts_data_size(rpc: &RpcClient, keys: &[Pubkey]) -> anyhow::Result<u64> {
+    let accounts = rpc.get_multiple_accounts(keys).await?;
+
+    let mut total = 0u64;
+    let mut program_data_keys = Vec::new();
+    for account in accounts.iter().flatten() {
+        total += account.data.len() as u64 + TRANSACTION_ACCOUNT_BASE_SIZE;
+        if bpf_loader_upgradeable::check_id(&account.owner) {
+            if let Ok((
+                UpgradeableLoaderState::Program {
+                    programdata_address,
+                },
+                _,
+            )) = bincode::serde::decode_from_slice(&account.data, bincode::config::legacy())
+            {
+                program_data_keys.push(programdata_address);
+            }
+        }
+    }
+
+    if !program_data_keys.is_empty() {
+        let program_data = rpc.get_multiple_accounts(&program_data_keys).await?;
+        for account in program_data.iter().flatten() {
+            total += account.data.len() as u64 + TRANSACTION_ACCOUNT_BASE_SIZE;
+        }
+    }
+
+    Ok(total)
+}
+
+fn with_margin(measured: u64, margin: f64) -> u64 {
+    (measured as f64 * (1.0 + margin.max(0.0))).ceil() as u64
+}
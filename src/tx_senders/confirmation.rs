@@ -0,0 +1,245 @@
+use crate::config::ConfirmationConfig;
+use crate::tx_senders::solana_rpc::TxMetrics;
+use crate::tx_senders::{BundleStatus, TxResult, TxSender};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// A transaction accepted by one sender, followed until it lands, fails or expires.
+pub struct SentTx {
+    pub sender: Arc<dyn TxSender>,
+    /// Position of the sender in `Bench::rpcs`.
+    pub index: u32,
+    pub signature: Signature,
+    /// The signed transaction, kept for rebroadcasting.
+    pub tx: VersionedTransaction,
+    pub result: TxResult,
+    pub sent_at: Instant,
+    /// Cancelled once this transaction reached a final state; stops its rebroadcasts.
+    pub settled: CancellationToken,
+}
+
+/// Final state of a tracked transaction.
+enum Outcome {
+    Landed { slot: u64, success: bool },
+    Failed(&'static str),
+}
+
+/// Polls signature (and bundle) statuses of every sender's transaction and emits one
+/// `TxMetrics` record per sender.
+pub struct ConfirmationTracker {
+    config: ConfirmationConfig,
+    rpc_client: Arc<RpcClient>,
+    metrics: mpsc::Sender<TxMetrics>,
+}
+
+impl ConfirmationTracker {
+    pub fn new(
+        config: ConfirmationConfig,
+        http_rpc: String,
+        metrics: mpsc::Sender<TxMetrics>,
+    ) -> Self {
+        Self {
+            config,
+            rpc_client: Arc::new(RpcClient::new_with_commitment(
+                http_rpc,
+                CommitmentConfig::confirmed(),
+            )),
+            metrics,
+        }
+    }
+
+    /// Fetches the processed slot in the background; started before a broadcast, it
+    /// yields the slot the transactions were sent in (0 if unavailable).
+    pub fn spawn_slot_query(&self) -> JoinHandle<u64> {
+        let rpc_client = self.rpc_client.clone();
+        tokio::spawn(async move {
+            match rpc_client
+                .get_slot_with_commitment(CommitmentConfig::processed())
+                .await
+            {
+                Ok(slot) => slot,
+                Err(e) => {
+                    warn!("failed to fetch send slot: {e}");
+                    0
+                }
+            }
+        })
+    }
+
+    /// Follows `sent` in the background. `last_valid_block_height` is `None` for
+    /// durable-nonce transactions, which only stop being tracked at `timeout_secs`.
+    /// With `exclusive` (nonce variants) the rest is settled as soon as one lands.
+    /// `done` is cancelled when the first status arrives or tracking ends.
+    pub fn track(
+        self: &Arc<Self>,
+        sent: Vec<SentTx>,
+        slot_sent: u64,
+        last_valid_block_height: Option<u64>,
+        exclusive: bool,
+        done: CancellationToken,
+    ) {
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            let _guard = done.clone().drop_guard();
+            tracker
+                .run(sent, slot_sent, last_valid_block_height, exclusive, &done)
+                .await
+        });
+    }
+
+    async fn run(
+        &self,
+        mut pending: Vec<SentTx>,
+        slot_sent: u64,
+        last_valid_block_height: Option<u64>,
+        exclusive: bool,
+        done: &CancellationToken,
+    ) {
+        if pending.is_empty() {
+            return;
+        }
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms.max(50));
+        let deadline = Instant::now() + Duration::from_secs(self.config.timeout_secs);
+
+        while !pending.is_empty() {
+            tokio::time::sleep(poll_interval).await;
+
+            // Read the height first so a transaction landing right before expiry is still seen.
+            let expired = match last_valid_block_height {
+                Some(last_valid) => match self.rpc_client.get_block_height().await {
+                    Ok(height) => height > last_valid,
+                    Err(e) => {
+                        warn!("block height poll failed: {e}");
+                        false
+                    }
+                },
+                None => false,
+            };
+
+            let signatures: Vec<Signature> = pending.iter().map(|tx| tx.signature).collect();
+            let statuses = match self.rpc_client.get_signature_statuses(&signatures).await {
+                Ok(response) => response.value,
+                Err(e) => {
+                    warn!("signature status poll failed: {e}");
+                    vec![None; signatures.len()]
+                }
+            };
+
+            let mut landed = false;
+            let mut still_pending = Vec::with_capacity(pending.len());
+            for (tx, status) in pending.into_iter().zip(statuses) {
+                if let Some(status) = status {
+                    landed = true;
+                    let success = status.err.is_none();
+                    self.emit(
+                        &tx,
+                        slot_sent,
+                        exclusive,
+                        Outcome::Landed {
+                            slot: status.slot,
+                            success,
+                        },
+                    )
+                    .await;
+                    continue;
+                }
+                if let TxResult::BundleID(bundle_id) = &tx.result {
+                    match tx.sender.get_bundle_status(bundle_id).await {
+                        Ok(Some(BundleStatus::Landed { slot })) => {
+                            landed = true;
+                            self.emit(
+                                &tx,
+                                slot_sent,
+                                exclusive,
+                                Outcome::Landed {
+                                    slot,
+                                    success: true,
+                                },
+                            )
+                            .await;
+                            continue;
+                        }
+                        Ok(Some(BundleStatus::Failed)) => {
+                            self.emit(&tx, slot_sent, exclusive, Outcome::Failed("bundle failed"))
+                                .await;
+                            continue;
+                        }
+                        Ok(Some(BundleStatus::Pending)) | Ok(None) => {}
+                        Err(e) => warn!("bundle status poll via {} failed: {e}", tx.sender.name()),
+                    }
+                }
+                still_pending.push(tx);
+            }
+            pending = still_pending;
+            if landed {
+                done.cancel();
+            }
+
+            if (exclusive && landed) || expired || Instant::now() >= deadline {
+                let reason = if exclusive && landed {
+                    "another variant landed"
+                } else if expired {
+                    "blockhash expired"
+                } else {
+                    "tracking timed out"
+                };
+                for tx in pending.drain(..) {
+                    self.emit(&tx, slot_sent, exclusive, Outcome::Failed(reason))
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Landings are attributable to one sender for exclusive nonce variants and for
+    /// bundles; otherwise all senders broadcast the same signature.
+    async fn emit(&self, tx: &SentTx, slot_sent: u64, exclusive: bool, outcome: Outcome) {
+        tx.settled.cancel();
+        let rpc_name = tx.sender.name();
+        let (success, slot_landed) = match outcome {
+            Outcome::Landed { slot, success } => {
+                info!(
+                    "{} tx {} landed in slot {} ({})",
+                    rpc_name,
+                    tx.signature,
+                    slot,
+                    if success { "ok" } else { "failed" }
+                );
+                (success, Some(slot))
+            }
+            Outcome::Failed(reason) => {
+                warn!("{} tx {} did not land: {}", rpc_name, tx.signature, reason);
+                (false, None)
+            }
+        };
+
+        let metrics = TxMetrics {
+            rpc_name,
+            signature: tx.signature.to_string(),
+            bundle_id: match &tx.result {
+                TxResult::BundleID(bundle_id) => Some(bundle_id.clone()),
+                TxResult::Signature(_) => None,
+            },
+            index: tx.index,
+            success,
+            slot_sent,
+            slot_landed,
+            slot_latency: slot_landed.map(|slot| slot.saturating_sub(slot_sent)),
+            elapsed: slot_landed.map(|_| tx.sent_at.elapsed().as_millis() as u64),
+            attributable: exclusive || matches!(tx.result, TxResult::BundleID(_)),
+        };
+        if let Err(e) = self.metrics.send(metrics).await {
+            warn!("dropping tx metrics: {e}");
+        }
+    }
+}
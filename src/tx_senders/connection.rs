@@ -0,0 +1,150 @@
+use crate::config::{ConnectionConfig, PingThingsArgs};
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::TxSender;
+
+use futures::future::join_all;
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// HTTP client with the pool and keep-alive settings of one sender.
+pub fn build_client(config: &ConnectionConfig) -> Client {
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+        .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .tcp_nodelay(true)
+        .tcp_keepalive(Duration::from_secs(60));
+    if config.http2_keep_alive_secs > 0 {
+        builder = builder
+            .http2_keep_alive_interval(Duration::from_secs(config.http2_keep_alive_secs))
+            .http2_keep_alive_timeout(Duration::from_secs(config.http2_keep_alive_timeout_secs))
+            .http2_keep_alive_while_idle(true);
+    }
+    builder.build().unwrap_or_else(|e| {
+        warn!("cannot build tuned HTTP client, using defaults: {}", e);
+        Client::new()
+    })
+}
+
+/// Opens (or reuses) a pooled connection to `url`. Any HTTP response counts: the point is
+/// the TCP/TLS/HTTP2 setup, not what the endpoint answers to an empty request.
+pub async fn ping_http(provider: &str, client: &Client, url: &str) -> anyhow::Result<()> {
+    client
+        .head(url)
+        .send()
+        .await
+        .map_err(|e| SendError::from_reqwest(provider, e))?;
+    Ok(())
+}
+
+struct ManagedSender {
+    sender: Arc<dyn TxSender>,
+    ping_interval: Duration,
+}
+
+/// Connects every sender before the bot goes live and keeps the connections warm between
+/// snipes, so the first transaction after an idle period does not pay for the handshakes.
+pub struct ConnectionManager {
+    senders: Vec<ManagedSender>,
+    ready_timeout: Duration,
+    warmup: bool,
+}
+
+impl ConnectionManager {
+    pub fn new(senders: &[Arc<dyn TxSender>], config: &PingThingsArgs) -> Self {
+        let senders = senders
+            .iter()
+            .map(|sender| {
+                let secs = config
+                    .rpc
+                    .get(&sender.name())
+                    .and_then(|rpc| rpc.ping_interval_secs)
+                    .unwrap_or(config.connections.ping_interval_secs);
+                ManagedSender {
+                    sender: sender.clone(),
+                    ping_interval: Duration::from_secs(secs),
+                }
+            })
+            .collect();
+        Self {
+            senders,
+            ready_timeout: Duration::from_secs(config.connections.ready_timeout_secs),
+            warmup: config.connections.warmup,
+        }
+    }
+
+    /// Connects all senders concurrently and reports their readiness. Returns the number of
+    /// senders ready within `ready_timeout_secs`.
+    pub async fn warm_up(&self) -> usize {
+        if !self.warmup {
+            return self.senders.len();
+        }
+        let results = join_all(self.senders.iter().map(|managed| async move {
+            let started = Instant::now();
+            let result = tokio::time::timeout(self.ready_timeout, managed.sender.warm_up()).await;
+            (managed, result, started.elapsed().as_millis() as u64)
+        }))
+        .await;
+
+        let mut ready = 0;
+        for (managed, result, connect_ms) in results {
+            let name = managed.sender.name();
+            let is_ready = match result {
+                Ok(Ok(())) => {
+                    info!("{} connected in {} ms", name, connect_ms);
+                    true
+                }
+                Ok(Err(e)) => {
+                    warn!("{} not ready: {:#}", name, e);
+                    false
+                }
+                Err(_) => {
+                    warn!("{} not ready after {:?}", name, self.ready_timeout);
+                    false
+                }
+            };
+            if is_ready {
+                ready += 1;
+            }
+            info!(
+                target: "metrics",
+                "{}",
+                json!({
+                    "sender": name,
+                    "ready": is_ready,
+                    "connect_ms": connect_ms,
+                })
+            );
+        }
+        ready
+    }
+
+    /// Pings every sender at its `ping_interval_secs` for the lifetime of the process.
+    pub fn spawn_keepalive(&self) {
+        for managed in &self.senders {
+            if managed.ping_interval.is_zero() {
+                continue;
+            }
+            let sender = managed.sender.clone();
+            let interval = managed.ping_interval;
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.tick().await;
+                loop {
+                    ticker.tick().await;
+                    if let Err(e) = sender.warm_up().await {
+                        debug!("{} keep-alive failed: {:#}", sender.name(), e);
+                    }
+                }
+            });
+        }
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.senders.len()
+    }
+}
@@ -0,0 +1,507 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::RpcError;
+use thiserror::Error;
+
+/// Classified send failure. Senders return it inside `anyhow::Error`; callers
+/// `downcast_ref::<SendError>()` to decide on retries, circuit breaking and alerts.
+#[derive(Debug, Error)]
+pub enum SendError {
+    /// The provider throttled us; back off.
+    #[error("{provider}: rate limited: {message}")]
+    RateLimited { provider: String, message: String },
+    /// Missing, wrong or expired credentials.
+    #[error("{provider}: unauthorized: {message}")]
+    Unauthorized { provider: String, message: String },
+    /// The transaction itself was refused (bad blockhash, signature, tip, simulation...).
+    #[error("{provider}: invalid transaction: {message}")]
+    InvalidTransaction { provider: String, message: String },
+    /// Timeout, connection or transport failure before a response arrived.
+    #[error("{provider}: network error: {message}")]
+    Network { provider: String, message: String },
+    /// Any other provider-side failure.
+    #[error("{provider}: provider error: {message}")]
+    Provider { provider: String, message: String },
+    /// The sender is not ready (e.g. not authenticated yet); nothing was sent.
+    #[error("{provider}: unavailable: {message}")]
+    Unavailable { provider: String, message: String },
+}
+
+/// `{"code": .., "message": ".."}` or `{"error": {"message": ".."}}` error bodies.
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    error: Option<ErrorMessage>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorMessage {
+    Text(String),
+    Object { message: String },
+}
+
+impl SendError {
+    /// Worth sending again later (possibly after backing off).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            SendError::RateLimited { .. }
+                | SendError::Network { .. }
+                | SendError::Provider { .. }
+                | SendError::Unavailable { .. }
+        )
+    }
+
+    /// Counts as a sender failure for the circuit breaker. An invalid transaction is
+    /// our fault, not the sender's, and an unavailable sender refused locally.
+    pub fn trips_breaker(&self) -> bool {
+        !matches!(
+            self,
+            SendError::InvalidTransaction { .. } | SendError::Unavailable { .. }
+        )
+    }
+
+    /// Needs operator attention rather than just a retry.
+    pub fn is_alert(&self) -> bool {
+        matches!(
+            self,
+            SendError::Unauthorized { .. } | SendError::InvalidTransaction { .. }
+        )
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SendError::RateLimited { .. } => "rate_limited",
+            SendError::Unauthorized { .. } => "unauthorized",
+            SendError::InvalidTransaction { .. } => "invalid_transaction",
+            SendError::Network { .. } => "network",
+            SendError::Provider { .. } => "provider",
+            SendError::Unavailable { .. } => "unavailable",
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        match self {
+            SendError::RateLimited { provider, .. }
+            | SendError::Unauthorized { provider, .. }
+            | SendError::InvalidTransaction { provider, .. }
+            | SendError::Network { provider, .. }
+            | SendError::Provider { provider, .. }
+            | SendError::Unavailable { provider, .. } => provider,
+        }
+    }
+
+    /// Transport errors of the HTTP relays.
+    pub fn from_reqwest(provider: &str, err: reqwest::Error) -> Self {
+        let provider = provider.to_string();
+        let message = err.to_string();
+        match err.status() {
+            Some(status) => classify_status(provider, status, message),
+            None => SendError::Network { provider, message },
+        }
+    }
+
+    /// Jito block-engine HTTP error bodies, e.g. `{"jsonrpc":"2.0","error":{"code":-32602,
+    /// "message":"bundle contains an expired blockhash"}}`.
+    pub fn from_jito(provider: &str, status: StatusCode, body: &str) -> Self {
+        let message = error_message(body);
+        let lower = message.to_lowercase();
+        let provider = provider.to_string();
+        if lower.contains("rate limit") || lower.contains("congested") {
+            SendError::RateLimited { provider, message }
+        } else if is_stale_transaction(&lower)
+            || lower.contains("tip account")
+            || lower.contains("invalid transaction")
+            || lower.contains("failed to deserialize")
+        {
+            SendError::InvalidTransaction { provider, message }
+        } else {
+            classify_status(provider, status, message)
+        }
+    }
+
+    /// bloXroute Trader API error bodies, e.g. `{"code":7,"message":"..."}`.
+    pub fn from_bloxroute(provider: &str, status: StatusCode, body: &str) -> Self {
+        let message = error_message(body);
+        let lower = message.to_lowercase();
+        let provider = provider.to_string();
+        if lower.contains("rate limit") || lower.contains("too many") || lower.contains("quota") {
+            SendError::RateLimited { provider, message }
+        } else if lower.contains("not authorized")
+            || lower.contains("authorization")
+            || (lower.contains("account") && (lower.contains("expired") || lower.contains("tier")))
+        {
+            SendError::Unauthorized { provider, message }
+        } else if is_stale_transaction(&lower)
+            || lower.contains("transaction")
+                && (lower.contains("invalid")
+                    || lower.contains("parse")
+                    || lower.contains("decode"))
+        {
+            SendError::InvalidTransaction { provider, message }
+        } else {
+            classify_status(provider, status, message)
+        }
+    }
+
+    /// NextBlock error bodies, e.g. `{"code":16,"message":"unauthorized"}`.
+    pub fn from_nextblock(provider: &str, status: StatusCode, body: &str) -> Self {
+        let message = error_message(body);
+        let lower = message.to_lowercase();
+        let provider = provider.to_string();
+        if lower.contains("rate limit") || lower.contains("too many") {
+            SendError::RateLimited { provider, message }
+        } else if lower.contains("unauthorized") || lower.contains("api key") {
+            SendError::Unauthorized { provider, message }
+        } else if is_stale_transaction(&lower)
+            || lower.contains("tip")
+            || lower.contains("invalid transaction")
+        {
+            SendError::InvalidTransaction { provider, message }
+        } else {
+            classify_status(provider, status, message)
+        }
+    }
+
+    /// Template relays: only the HTTP status and common wording are known.
+    pub fn from_http(provider: &str, status: StatusCode, body: &str) -> Self {
+        let message = error_message(body);
+        let lower = message.to_lowercase();
+        let provider = provider.to_string();
+        if lower.contains("rate limit") || lower.contains("too many requests") {
+            SendError::RateLimited { provider, message }
+        } else if is_stale_transaction(&lower) || lower.contains("invalid transaction") {
+            SendError::InvalidTransaction { provider, message }
+        } else {
+            classify_status(provider, status, message)
+        }
+    }
+
+    /// Solana JSON-RPC client errors.
+    pub fn from_rpc(provider: &str, err: ClientError) -> Self {
+        let provider = provider.to_string();
+        let message = err.to_string();
+        match err.kind() {
+            ClientErrorKind::Io(_) => SendError::Network { provider, message },
+            // The RPC client uses its own reqwest version; compare the numeric status.
+            ClientErrorKind::Reqwest(e) => {
+                match e
+                    .status()
+                    .and_then(|s| StatusCode::from_u16(s.as_u16()).ok())
+                {
+                    Some(status) => classify_status(provider, status, message),
+                    None => SendError::Network { provider, message },
+                }
+            }
+            ClientErrorKind::TransactionError(_) | ClientErrorKind::SigningError(_) => {
+                SendError::InvalidTransaction { provider, message }
+            }
+            // -32002 preflight failure, -32003 signature verification failure,
+            // -32013 signature length mismatch.
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })
+                if matches!(code, -32002 | -32003 | -32013) =>
+            {
+                SendError::InvalidTransaction { provider, message }
+            }
+            ClientErrorKind::RpcError(RpcError::ForUser(text)) if text.contains("429") => {
+                SendError::RateLimited { provider, message }
+            }
+            _ => {
+                let lower = message.to_lowercase();
+                if lower.contains("429") || lower.contains("too many requests") {
+                    SendError::RateLimited { provider, message }
+                } else if lower.contains("401") || lower.contains("403") {
+                    SendError::Unauthorized { provider, message }
+                } else {
+                    SendError::Provider { provider, message }
+                }
+            }
+        }
+    }
+
+    /// gRPC status codes of the block-engine searcher API.
+    pub fn from_grpc(provider: &str, status: &tonic::Status) -> Self {
+        use tonic::Code;
+        let provider = provider.to_string();
+        let message = status.message().to_string();
+        match status.code() {
+            Code::ResourceExhausted => SendError::RateLimited { provider, message },
+            Code::Unauthenticated | Code::PermissionDenied => {
+                SendError::Unauthorized { provider, message }
+            }
+            Code::InvalidArgument | Code::FailedPrecondition => {
+                SendError::InvalidTransaction { provider, message }
+            }
+            Code::Unavailable | Code::DeadlineExceeded | Code::Cancelled => {
+                SendError::Network { provider, message }
+            }
+            _ => SendError::Provider { provider, message },
+        }
+    }
+}
+
+fn classify_status(provider: String, status: StatusCode, message: String) -> SendError {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => SendError::RateLimited { provider, message },
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            SendError::Unauthorized { provider, message }
+        }
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => {
+            SendError::Network { provider, message }
+        }
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+            SendError::InvalidTransaction { provider, message }
+        }
+        _ => SendError::Provider {
+            provider,
+            message: format!("HTTP {}: {}", status.as_u16(), message),
+        },
+    }
+}
+
+/// Expired or unknown blockhash, or a transaction that already landed; `lower` is the
+/// lower-cased message.
+fn is_stale_transaction(lower: &str) -> bool {
+    lower.contains("blockhash")
+        || lower.contains("already processed")
+        || lower.contains("already been processed")
+}
+
+/// The human-readable message of a JSON error body, or the raw body.
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<ErrorBody>(body) {
+        Ok(ErrorBody {
+            error: Some(ErrorMessage::Object { message }),
+            ..
+        })
+        | Ok(ErrorBody {
+            error: Some(ErrorMessage::Text(message)),
+            ..
+        })
+        | Ok(ErrorBody {
+            message: Some(message),
+            ..
+        }) => message,
+        _ => body.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcResponseErrorData;
+
+    type Classify = fn(&str, StatusCode, &str) -> SendError;
+
+    /// `(status, body, expected kind)` per case, all classified with `classify`.
+    fn assert_kinds(classify: Classify, cases: &[(u16, &str, &str)]) {
+        for (status, body, expected) in cases {
+            let status = StatusCode::from_u16(*status).unwrap();
+            let err = classify("provider", status, body);
+            assert_eq!(err.kind(), *expected, "{status} {body}");
+        }
+    }
+
+    /// Block-engine JSON-RPC error body.
+    fn jsonrpc_error(code: i64, message: &str) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "error": { "code": code, "message": message },
+            "id": 1,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn classifies_jito_bodies() {
+        let rate_limited = jsonrpc_error(
+            -32097,
+            "Rate limit exceeded. Limit: 1 per second for txn requests",
+        );
+        let expired = jsonrpc_error(-32602, "bundle contains an expired blockhash");
+        let processed = jsonrpc_error(-32602, "bundle contains an already processed transaction");
+        let unauthorized = jsonrpc_error(-32600, "The supplied UUID is not authorized");
+        let unknown = jsonrpc_error(-32603, "Internal error");
+        assert_kinds(
+            SendError::from_jito,
+            &[
+                (429, rate_limited.as_str(), "rate_limited"),
+                (400, expired.as_str(), "invalid_transaction"),
+                (400, processed.as_str(), "invalid_transaction"),
+                (401, unauthorized.as_str(), "unauthorized"),
+                (500, unknown.as_str(), "provider"),
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_bloxroute_bodies() {
+        assert_kinds(
+            SendError::from_bloxroute,
+            &[
+                (
+                    429,
+                    r#"{"code":8,"message":"rate limit exceeded, please try again later"}"#,
+                    "rate_limited",
+                ),
+                (
+                    500,
+                    r#"{"code":2,"message":"failed to send transaction: Blockhash not found"}"#,
+                    "invalid_transaction",
+                ),
+                (
+                    500,
+                    r#"{"code":2,"message":"This transaction has already been processed"}"#,
+                    "invalid_transaction",
+                ),
+                (
+                    403,
+                    r#"{"code":7,"message":"account is not authorized to use this endpoint"}"#,
+                    "unauthorized",
+                ),
+                (
+                    500,
+                    r#"{"code":13,"message":"internal server error"}"#,
+                    "provider",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_nextblock_bodies() {
+        assert_kinds(
+            SendError::from_nextblock,
+            &[
+                (
+                    429,
+                    r#"{"code":8,"message":"rate limit exceeded"}"#,
+                    "rate_limited",
+                ),
+                (
+                    500,
+                    r#"{"code":2,"message":"transaction blockhash expired"}"#,
+                    "invalid_transaction",
+                ),
+                (
+                    500,
+                    r#"{"code":2,"message":"transaction already processed"}"#,
+                    "invalid_transaction",
+                ),
+                (
+                    401,
+                    r#"{"code":16,"message":"unauthorized"}"#,
+                    "unauthorized",
+                ),
+                (500, r#"{"code":13,"message":"internal error"}"#, "provider"),
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_relay_bodies() {
+        assert_kinds(
+            SendError::from_http,
+            &[
+                (429, "Too Many Requests", "rate_limited"),
+                (
+                    500,
+                    r#"{"error":{"message":"Blockhash not found"}}"#,
+                    "invalid_transaction",
+                ),
+                (
+                    500,
+                    r#"{"error":"This transaction has already been processed"}"#,
+                    "invalid_transaction",
+                ),
+                (401, r#"{"error":"invalid api key"}"#, "unauthorized"),
+                (502, "Bad Gateway", "provider"),
+            ],
+        );
+    }
+
+    fn rpc_response_error(code: i64, message: &str) -> ClientError {
+        RpcError::RpcResponseError {
+            code,
+            message: message.to_string(),
+            data: RpcResponseErrorData::Empty,
+        }
+        .into()
+    }
+
+    #[test]
+    fn classifies_rpc_errors() {
+        let cases = [
+            (
+                rpc_response_error(
+                    429,
+                    "Too many requests for a specific RPC call, contact your app developer or \
+                     support@rpcpool.com.",
+                ),
+                "rate_limited",
+            ),
+            (
+                rpc_response_error(-32002, "Transaction simulation failed: Blockhash not found"),
+                "invalid_transaction",
+            ),
+            (
+                rpc_response_error(
+                    -32002,
+                    "Transaction simulation failed: This transaction has already been processed",
+                ),
+                "invalid_transaction",
+            ),
+            (
+                RpcError::ForUser(
+                    "HTTP status client error (401 Unauthorized) for url (https://rpc.example)"
+                        .to_string(),
+                )
+                .into(),
+                "unauthorized",
+            ),
+            (rpc_response_error(-32603, "Internal error"), "provider"),
+        ];
+        for (err, expected) in cases {
+            let message = err.to_string();
+            assert_eq!(
+                SendError::from_rpc("rpc", err).kind(),
+                expected,
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_grpc_statuses() {
+        let cases = [
+            (
+                tonic::Status::resource_exhausted("bundle rate limit exceeded"),
+                "rate_limited",
+            ),
+            (
+                tonic::Status::invalid_argument("bundle contains an expired blockhash"),
+                "invalid_transaction",
+            ),
+            (
+                tonic::Status::invalid_argument("bundle contains an already processed transaction"),
+                "invalid_transaction",
+            ),
+            (
+                tonic::Status::unauthenticated("The access token has expired"),
+                "unauthorized",
+            ),
+            (tonic::Status::internal("internal error"), "provider"),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(
+                SendError::from_grpc("jito-grpc", &status).kind(),
+                expected,
+                "{status:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::config::{RpcType, SenderGuardConfig};
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::{BundleStatus, TxResult, TxSender};
+
+use async_trait::async_trait;
+use serde_json::json;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Sends refused locally, without reaching the provider.
+#[derive(Debug, Error)]
+pub enum GuardError {
+    #[error("{0}: rate limit reached")]
+    RateLimited(String),
+    #[error("{0}: circuit open")]
+    CircuitOpen(String),
+}
+
+/// Rate-limit tokens a resend must leave in the bucket.
+const RESEND_RESERVE: f64 = 1.0;
+
+struct TokenBucket {
+    /// Tokens per second; 0 disables limiting.
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            rate,
+            burst,
+            tokens: burst,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Takes a token if at least `reserve` more stay in the bucket (capped so a full
+    /// bucket can always give one).
+    fn try_take(&mut self, reserve: f64) -> bool {
+        if self.rate <= 0.0 {
+            return true;
+        }
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled_at = now;
+        if self.tokens >= 1.0 + reserve.min(self.burst - 1.0) {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+struct CircuitBreaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    /// A half-open probe is on its way; other sends are refused until it returns.
+    probing: bool,
+}
+
+/// Held by the send that carries the half-open probe; clears `probing` when the send ends,
+/// including when its future is dropped before the result is recorded.
+struct ProbeGuard<'a>(&'a Mutex<CircuitBreaker>);
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().probing = false;
+    }
+}
+
+/// Wraps a sender with a token-bucket rate limit and a circuit breaker that opens after
+/// `failure_threshold` consecutive failures and lets a single probe through after `open_secs`.
+pub struct GuardedSender {
+    inner: Arc<dyn TxSender>,
+    config: SenderGuardConfig,
+    bucket: Mutex<TokenBucket>,
+    breaker: Mutex<CircuitBreaker>,
+}
+
+impl GuardedSender {
+    pub fn new(inner: Arc<dyn TxSender>, config: SenderGuardConfig) -> Self {
+        Self {
+            bucket: Mutex::new(TokenBucket::new(config.rate_limit_per_sec, config.burst)),
+            breaker: Mutex::new(CircuitBreaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+                probing: false,
+            }),
+            inner,
+            config,
+        }
+    }
+
+    /// Admits a send, moving an expired open circuit to half-open, and takes a rate-limit
+    /// token that leaves `reserve` behind. The returned guard is set when this send is the
+    /// half-open probe.
+    fn admit(&self, reserve: f64) -> Result<Option<ProbeGuard<'_>>, GuardError> {
+        let probe = {
+            let mut breaker = self.breaker.lock().unwrap();
+            match breaker.state {
+                CircuitState::Closed => None,
+                CircuitState::Open => {
+                    let open_for = Duration::from_secs(self.config.open_secs);
+                    if breaker.opened_at.elapsed() < open_for {
+                        return Err(GuardError::CircuitOpen(self.inner.name()));
+                    }
+                    self.transition(&mut breaker, CircuitState::HalfOpen);
+                    breaker.probing = true;
+                    Some(ProbeGuard(&self.breaker))
+                }
+                CircuitState::HalfOpen => {
+                    if breaker.probing {
+                        return Err(GuardError::CircuitOpen(self.inner.name()));
+                    }
+                    breaker.probing = true;
+                    Some(ProbeGuard(&self.breaker))
+                }
+            }
+        };
+        if !self.bucket.lock().unwrap().try_take(reserve) {
+            return Err(GuardError::RateLimited(self.inner.name()));
+        }
+        Ok(probe)
+    }
+
+    async fn send(&self, tx: &VersionedTransaction, reserve: f64) -> anyhow::Result<TxResult> {
+        let _probe = self.admit(reserve)?;
+        let result = self.inner.send_transaction(tx).await;
+        self.record(&result);
+        result
+    }
+
+    fn record(&self, result: &anyhow::Result<TxResult>) {
+        let mut breaker = self.breaker.lock().unwrap();
+        breaker.probing = false;
+        // A transaction the provider rejected as invalid says nothing about its health.
+        if let Err(e) = result {
+            if e.downcast_ref::<SendError>()
+                .is_some_and(|e| !e.trips_breaker())
+            {
+                return;
+            }
+        }
+        if result.is_ok() {
+            breaker.consecutive_failures = 0;
+            if breaker.state != CircuitState::Closed {
+                self.transition(&mut breaker, CircuitState::Closed);
+            }
+            return;
+        }
+
+        breaker.consecutive_failures += 1;
+        let trip = match breaker.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => {
+                self.config.failure_threshold > 0
+                    && breaker.consecutive_failures >= self.config.failure_threshold
+            }
+            CircuitState::Open => false,
+        };
+        if trip {
+            breaker.opened_at = Instant::now();
+            self.transition(&mut breaker, CircuitState::Open);
+        }
+    }
+
+    fn transition(&self, breaker: &mut CircuitBreaker, state: CircuitState) {
+        breaker.state = state;
+        let name = self.inner.name();
+        match state {
+            CircuitState::Open => warn!(
+                "{} circuit open after {} consecutive failures, retry in {}s",
+                name, breaker.consecutive_failures, self.config.open_secs
+            ),
+            CircuitState::HalfOpen => info!("{} circuit half-open, probing", name),
+            CircuitState::Closed => info!("{} circuit closed", name),
+        }
+        info!(
+            target: "metrics",
+            "{}",
+            json!({
+                "sender": name,
+                "circuit": format!("{:?}", state).to_lowercase(),
+                "consecutive_failures": breaker.consecutive_failures,
+            })
+        );
+    }
+}
+
+#[async_trait]
+impl TxSender for GuardedSender {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn rpc_type(&self) -> RpcType {
+        self.inner.rpc_type()
+    }
+
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        self.send(tx, 0.0).await
+    }
+
+    /// Takes a token like any send, but is refused while taking it would leave fewer than
+    /// `RESEND_RESERVE` for the first send of the next snipe.
+    async fn resend_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        self.send(tx, RESEND_RESERVE).await
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        self.inner.get_block_height().await
+    }
+
+    /// Not a send: bypasses the rate limit and the breaker.
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        self.inner.warm_up().await
+    }
+
+    async fn get_bundle_status(&self, bundle_id: &str) -> anyhow::Result<Option<BundleStatus>> {
+        self.inner.get_bundle_status(bundle_id).await
+    }
+}
@@ -1,78 +1,101 @@
 use crate::config::RpcType;
-use crate::meteora::types::MeteoraSwapParams;
-use crate::tx_senders::transaction::{build_meteora_swap_tx, TransactionConfig};
-use crate::tx_senders::{TxResult, TxSender};
+use crate::tx_senders::connection::ping_http;
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::{BundleStatus, TxResult, TxSender};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use bincode::config;
 use bincode::serde as bincode_serde;
 use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::hash::Hash;
+use solana_sdk::transaction::VersionedTransaction;
 use std::sync::Arc;
 use tracing::info;
 
 pub struct JitoTxSender {
     url: String,
+    /// JSON-RPC bundles endpoint of the same block-engine, for status lookups.
+    bundles_url: String,
     name: String,
     client: Client,
-    tx_config: TransactionConfig,
     /// For getting block height/checking status
     rpc_client: Arc<RpcClient>,
 }
 
 impl JitoTxSender {
-    pub fn new(
-        name: String,
-        url: String,
-        tx_config: TransactionConfig,
-        client: Client,
-        rpc_client: Arc<RpcClient>,
-    ) -> Self {
+    pub fn new(name: String, url: String, client: Client, rpc_client: Arc<RpcClient>) -> Self {
         Self {
+            bundles_url: bundles_url(&url),
             url,
             name,
             client,
-            tx_config,
             rpc_client,
         }
     }
 }
 
+#[derive(Deserialize)]
+struct InflightBundleStatuses {
+    result: InflightBundleResult,
+}
+
+#[derive(Deserialize)]
+struct InflightBundleResult {
+    value: Vec<Option<InflightBundle>>,
+}
+
+#[derive(Deserialize)]
+struct InflightBundle {
+    status: String,
+    landed_slot: Option<u64>,
+}
+
+/// `https://host/api/v1/transactions` -> `https://host/api/v1/bundles`
+fn bundles_url(url: &str) -> String {
+    let base = match url.find("/api/v1") {
+        Some(i) => &url[..i],
+        None => url.trim_end_matches('/'),
+    };
+    format!("{base}/api/v1/bundles")
+}
+
 #[async_trait]
 impl TxSender for JitoTxSender {
     fn name(&self) -> String {
         self.name.clone()
     }
 
+    fn rpc_type(&self) -> RpcType {
+        RpcType::Jito
+    }
+
     /// Send a single swap transaction as a raw-bundle to the block-engine.
-    async fn send_meteora_swap(
-        &self,
-        params: &MeteoraSwapParams,
-        recent_blockhash: Hash,
-    ) -> anyhow::Result<TxResult> {
-        // 1. Build VersionedTransaction
-        let tx = build_meteora_swap_tx(&self.tx_config, &RpcType::Jito, recent_blockhash, params);
-
-        // 2. Serialize to raw bytes (bincode) — this is exactly what block-engine expects.
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        // 1. Serialize to raw bytes (bincode) — this is exactly what block-engine expects.
         let config = config::standard();
-        let tx_bytes = bincode_serde::encode_to_vec(&tx, config).context("cannot serialize tx")?;
+        let tx_bytes = bincode_serde::encode_to_vec(tx, config).context("cannot serialize tx")?;
 
-        // 3. Send as `application/octet-stream`
+        // 2. Send as `application/octet-stream`
         let resp = self
             .client
             .post(&self.url)
             .header("Content-Type", "application/octet-stream")
             .body(tx_bytes)
             .send()
-            .await?;
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
 
         let status = resp.status();
-        let body = resp.text().await?;
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
 
         if !status.is_success() {
-            return Err(anyhow::anyhow!("bundle submit failed: {}", body));
+            return Err(SendError::from_jito(&self.name, status, &body).into());
         }
 
         // block-engine returns a BundleID string in JSON (usually just `"uuid"`).
@@ -81,8 +104,44 @@ impl TxSender for JitoTxSender {
         Ok(TxResult::BundleID(bundle_id))
     }
 
+    /// `getInflightBundleStatuses`; unknown bundles (`Invalid`) are still treated as pending.
+    async fn get_bundle_status(&self, bundle_id: &str) -> anyhow::Result<Option<BundleStatus>> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getInflightBundleStatuses",
+            "params": [[bundle_id]],
+        });
+        let text = self
+            .client
+            .post(&self.bundles_url)
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_string(&body)?)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let resp: InflightBundleStatuses =
+            serde_json::from_str(&text).context("invalid getInflightBundleStatuses response")?;
+
+        let Some(Some(bundle)) = resp.result.value.into_iter().next() else {
+            return Ok(Some(BundleStatus::Pending));
+        };
+        let status = match (bundle.status.as_str(), bundle.landed_slot) {
+            ("Landed", Some(slot)) => BundleStatus::Landed { slot },
+            ("Failed", _) => BundleStatus::Failed,
+            _ => BundleStatus::Pending,
+        };
+        Ok(Some(status))
+    }
+
     /// For logs/metrics, a regular RPC client can be called
     async fn get_block_height(&self) -> anyhow::Result<u64> {
         Ok(self.rpc_client.get_block_height().await?)
     }
+
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        ping_http(&self.name, &self.client, &self.url).await
+    }
 }
@@ -0,0 +1,1028 @@
+use crate::config::RpcType;
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::{BundleStatus, TxResult, TxSender};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use bincode::config;
+use bincode::serde as bincode_serde;
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tracing::{debug, info, warn};
+
+/// Bundle results kept for `get_bundle_status` lookups.
+const MAX_BUNDLE_RESULTS: usize = 1024;
+/// Access tokens are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+/// Delay before a failed authentication is retried in the background.
+const AUTH_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// How long `warm_up` waits for the background authentication.
+const AUTH_WAIT: Duration = Duration::from_secs(10);
+/// Delay before the bundle-result subscription is re-opened.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+/// HTTP/2 PINGs keep the block-engine channel open between snipes.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Hand-written subset of the block-engine protos (`auth`, `searcher`, `bundle`, `packet`).
+mod proto {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    #[allow(dead_code)]
+    pub enum Role {
+        Relayer = 0,
+        Searcher = 1,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GenerateAuthChallengeRequest {
+        #[prost(enumeration = "Role", tag = "1")]
+        pub role: i32,
+        #[prost(bytes = "vec", tag = "2")]
+        pub pubkey: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GenerateAuthChallengeResponse {
+        #[prost(string, tag = "1")]
+        pub challenge: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GenerateAuthTokensRequest {
+        #[prost(string, tag = "1")]
+        pub challenge: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub client_pubkey: Vec<u8>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub signed_challenge: Vec<u8>,
+    }
+
+    /// `google.protobuf.Timestamp`
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Timestamp {
+        #[prost(int64, tag = "1")]
+        pub seconds: i64,
+        #[prost(int32, tag = "2")]
+        pub nanos: i32,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Token {
+        #[prost(string, tag = "1")]
+        pub value: String,
+        #[prost(message, optional, tag = "2")]
+        pub expires_at_utc: Option<Timestamp>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GenerateAuthTokensResponse {
+        #[prost(message, optional, tag = "1")]
+        pub access_token: Option<Token>,
+        #[prost(message, optional, tag = "2")]
+        pub refresh_token: Option<Token>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RefreshAccessTokenRequest {
+        #[prost(string, tag = "1")]
+        pub refresh_token: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RefreshAccessTokenResponse {
+        #[prost(message, optional, tag = "1")]
+        pub access_token: Option<Token>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Meta {
+        #[prost(uint64, tag = "1")]
+        pub size: u64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Packet {
+        #[prost(bytes = "vec", tag = "1")]
+        pub data: Vec<u8>,
+        #[prost(message, optional, tag = "2")]
+        pub meta: Option<Meta>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Bundle {
+        #[prost(message, repeated, tag = "3")]
+        pub packets: Vec<Packet>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SendBundleRequest {
+        #[prost(message, optional, tag = "1")]
+        pub bundle: Option<Bundle>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SendBundleResponse {
+        #[prost(string, tag = "1")]
+        pub uuid: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SubscribeBundleResultsRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Accepted {
+        #[prost(uint64, tag = "1")]
+        pub slot: u64,
+        #[prost(string, tag = "2")]
+        pub validator_identity: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Processed {
+        #[prost(string, tag = "1")]
+        pub validator_identity: String,
+        #[prost(uint64, tag = "2")]
+        pub slot: u64,
+        #[prost(uint64, tag = "3")]
+        pub bundle_index: u64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Finalized {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Dropped {
+        #[prost(int32, tag = "1")]
+        pub reason: i32,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SimulationFailure {
+        #[prost(string, tag = "1")]
+        pub tx_signature: String,
+        #[prost(string, optional, tag = "2")]
+        pub msg: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ReasonMessage {
+        #[prost(string, tag = "1")]
+        pub msg: String,
+    }
+
+    /// Auction rejections are not decoded; they only carry bid details.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Rejected {
+        #[prost(oneof = "rejected::Reason", tags = "3, 4, 5")]
+        pub reason: Option<rejected::Reason>,
+    }
+
+    pub mod rejected {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Reason {
+            #[prost(message, tag = "3")]
+            SimulationFailure(super::SimulationFailure),
+            #[prost(message, tag = "4")]
+            InternalError(super::ReasonMessage),
+            #[prost(message, tag = "5")]
+            DroppedBundle(super::ReasonMessage),
+        }
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct BundleResult {
+        #[prost(string, tag = "1")]
+        pub bundle_id: String,
+        #[prost(oneof = "bundle_result::Result", tags = "2, 3, 4, 5, 6")]
+        pub result: Option<bundle_result::Result>,
+    }
+
+    pub mod bundle_result {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Result {
+            #[prost(message, tag = "2")]
+            Accepted(super::Accepted),
+            #[prost(message, tag = "3")]
+            Rejected(super::Rejected),
+            #[prost(message, tag = "4")]
+            Finalized(super::Finalized),
+            #[prost(message, tag = "5")]
+            Processed(super::Processed),
+            #[prost(message, tag = "6")]
+            Dropped(super::Dropped),
+        }
+    }
+}
+
+use proto::bundle_result::Result as BundleOutcome;
+
+const GENERATE_AUTH_CHALLENGE: &str = "/auth.AuthService/GenerateAuthChallenge";
+const GENERATE_AUTH_TOKENS: &str = "/auth.AuthService/GenerateAuthTokens";
+const REFRESH_ACCESS_TOKEN: &str = "/auth.AuthService/RefreshAccessToken";
+const SEND_BUNDLE: &str = "/searcher.SearcherService/SendBundle";
+const SUBSCRIBE_BUNDLE_RESULTS: &str = "/searcher.SearcherService/SubscribeBundleResults";
+
+struct Tokens {
+    access: String,
+    access_expires_at: i64,
+    refresh: String,
+}
+
+/// Latest bundle outcomes reported by the subscription, oldest evicted first.
+#[derive(Default)]
+struct BundleResults {
+    statuses: HashMap<String, BundleStatus>,
+    order: VecDeque<String>,
+}
+
+impl BundleResults {
+    fn record(&mut self, bundle_id: String, status: BundleStatus) {
+        if self.statuses.insert(bundle_id.clone(), status).is_none() {
+            self.order.push_back(bundle_id);
+            if self.order.len() > MAX_BUNDLE_RESULTS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.statuses.remove(&oldest);
+                }
+            }
+        }
+    }
+}
+
+struct Inner {
+    name: String,
+    channel: Channel,
+    /// Searcher keypair for the auth challenge; `None` sends unauthenticated requests.
+    auth_keypair: Option<Keypair>,
+    tokens: RwLock<Option<Tokens>>,
+    /// Whether a valid access token is held; sends fail fast while it is `false`.
+    authenticated: watch::Sender<bool>,
+    results: Mutex<BundleResults>,
+}
+
+/// Submits bundles through the block-engine's gRPC searcher API and follows their
+/// outcomes through the bundle-result stream.
+pub struct JitoGrpcTxSender {
+    inner: Arc<Inner>,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl JitoGrpcTxSender {
+    pub fn new(
+        name: String,
+        url: String,
+        auth_keypair: Option<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> anyhow::Result<Self> {
+        let mut endpoint = Endpoint::from_shared(url.clone())?
+            .tcp_nodelay(true)
+            .http2_keep_alive_interval(KEEP_ALIVE_INTERVAL)
+            .keep_alive_timeout(KEEP_ALIVE_TIMEOUT)
+            .keep_alive_while_idle(true);
+        if url.starts_with("https://") {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new().with_enabled_roots())?;
+        }
+        let channel = endpoint.connect_lazy();
+        let inner = Arc::new(Inner {
+            name,
+            channel,
+            auth_keypair,
+            tokens: RwLock::new(None),
+            authenticated: watch::Sender::new(false),
+            results: Mutex::new(BundleResults::default()),
+        });
+
+        // Authenticate and subscribe before the first snipe needs it; authentication is
+        // only ever retried here, never on the send path.
+        if inner.auth_keypair.is_some() {
+            tokio::spawn(inner.clone().auth_loop());
+        }
+        tokio::spawn(inner.clone().subscribe_loop());
+
+        Ok(Self { inner, rpc_client })
+    }
+}
+
+impl Inner {
+    /// Fails with `SendError::Unavailable` while a configured searcher keypair has not
+    /// authenticated yet.
+    fn check_authenticated(&self) -> Result<(), SendError> {
+        if self.auth_keypair.is_none() || *self.authenticated.borrow() {
+            return Ok(());
+        }
+        Err(SendError::Unavailable {
+            provider: self.name.clone(),
+            message: "not authenticated".to_string(),
+        })
+    }
+
+    async fn unary<Req, Resp>(
+        &self,
+        path: &'static str,
+        message: Req,
+        authorized: bool,
+    ) -> anyhow::Result<Resp>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready().await?;
+        let request = self.request(message, authorized)?;
+        let response = grpc
+            .unary(
+                request,
+                PathAndQuery::from_static(path),
+                ProstCodec::<Req, Resp>::default(),
+            )
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    fn request<T>(&self, message: T, authorized: bool) -> anyhow::Result<tonic::Request<T>> {
+        let mut request = tonic::Request::new(message);
+        if authorized {
+            if let Some(tokens) = self.tokens.read().unwrap().as_ref() {
+                request.metadata_mut().insert(
+                    "authorization",
+                    format!("Bearer {}", tokens.access).parse()?,
+                );
+            }
+        }
+        Ok(request)
+    }
+
+    /// Challenge-response login: sign `"<pubkey>-<challenge>"` with the searcher keypair.
+    async fn authenticate(&self) -> anyhow::Result<()> {
+        let keypair = self
+            .auth_keypair
+            .as_ref()
+            .context("no auth keypair configured")?;
+        let pubkey = keypair.pubkey();
+
+        let challenge: proto::GenerateAuthChallengeResponse = self
+            .unary(
+                GENERATE_AUTH_CHALLENGE,
+                proto::GenerateAuthChallengeRequest {
+                    role: proto::Role::Searcher as i32,
+                    pubkey: pubkey.to_bytes().to_vec(),
+                },
+                false,
+            )
+            .await
+            .context("auth challenge failed")?;
+
+        let challenge = format!("{}-{}", pubkey, challenge.challenge);
+        let signed_challenge = keypair.sign_message(challenge.as_bytes());
+        let tokens: proto::GenerateAuthTokensResponse = self
+            .unary(
+                GENERATE_AUTH_TOKENS,
+                proto::GenerateAuthTokensRequest {
+                    challenge,
+                    client_pubkey: pubkey.to_bytes().to_vec(),
+                    signed_challenge: signed_challenge.as_ref().to_vec(),
+                },
+                false,
+            )
+            .await
+            .context("auth token request failed")?;
+
+        let (Some(access), Some(refresh)) = (tokens.access_token, tokens.refresh_token) else {
+            anyhow::bail!("block-engine returned no auth tokens");
+        };
+        *self.tokens.write().unwrap() = Some(Tokens {
+            access_expires_at: expires_at(&access),
+            access: access.value,
+            refresh: refresh.value,
+        });
+        self.authenticated.send_replace(true);
+        info!("{}: authenticated as searcher {}", self.name, pubkey);
+        Ok(())
+    }
+
+    /// Authenticates, then refreshes the access token shortly before expiry and
+    /// re-authenticates if that fails. Failures are retried every `AUTH_RETRY_DELAY`; once
+    /// the access token has expired, sends fail fast until authentication succeeds again.
+    async fn auth_loop(self: Arc<Self>) {
+        loop {
+            let current = self
+                .tokens
+                .read()
+                .unwrap()
+                .as_ref()
+                .map(|tokens| (tokens.refresh.clone(), tokens.access_expires_at));
+            let Some((refresh, access_expires_at)) = current else {
+                if let Err(e) = self.authenticate().await {
+                    warn!("{}: authentication failed: {:#}", self.name, e);
+                    tokio::time::sleep(AUTH_RETRY_DELAY).await;
+                }
+                continue;
+            };
+            let wait = Duration::from_secs((access_expires_at - unix_now()).max(0) as u64)
+                .saturating_sub(TOKEN_REFRESH_MARGIN)
+                .max(Duration::from_secs(1));
+            tokio::time::sleep(wait).await;
+
+            let refreshed: anyhow::Result<proto::RefreshAccessTokenResponse> = self
+                .unary(
+                    REFRESH_ACCESS_TOKEN,
+                    proto::RefreshAccessTokenRequest {
+                        refresh_token: refresh,
+                    },
+                    false,
+                )
+                .await;
+            match refreshed.map(|resp| resp.access_token) {
+                Ok(Some(access)) => {
+                    if let Some(tokens) = self.tokens.write().unwrap().as_mut() {
+                        tokens.access_expires_at = expires_at(&access);
+                        tokens.access = access.value;
+                    }
+                    debug!("{}: access token refreshed", self.name);
+                }
+                Ok(None) | Err(_) => {
+                    warn!("{}: token refresh failed, re-authenticating", self.name);
+                    if let Err(e) = self.authenticate().await {
+                        warn!("{}: re-authentication failed: {:#}", self.name, e);
+                        if unix_now() >= access_expires_at {
+                            *self.tokens.write().unwrap() = None;
+                            self.authenticated.send_replace(false);
+                        }
+                        tokio::time::sleep(AUTH_RETRY_DELAY).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keeps the bundle-result stream open, while authenticated if a searcher keypair is
+    /// configured, and records every outcome.
+    async fn subscribe_loop(self: Arc<Self>) {
+        let mut authenticated = self.authenticated.subscribe();
+        loop {
+            if self.auth_keypair.is_some() && authenticated.wait_for(|ok| *ok).await.is_err() {
+                return;
+            }
+            if let Err(e) = self.subscribe_once().await {
+                warn!("{}: bundle result stream failed: {:#}", self.name, e);
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    async fn subscribe_once(&self) -> anyhow::Result<()> {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready().await?;
+        let request = self.request(proto::SubscribeBundleResultsRequest {}, true)?;
+        let mut stream = grpc
+            .server_streaming(
+                request,
+                PathAndQuery::from_static(SUBSCRIBE_BUNDLE_RESULTS),
+                ProstCodec::<proto::SubscribeBundleResultsRequest, proto::BundleResult>::default(),
+            )
+            .await?
+            .into_inner();
+
+        while let Some(result) = stream.next().await {
+            let result = result?;
+            let Some(outcome) = result.result else {
+                continue;
+            };
+            let status = match outcome {
+                BundleOutcome::Accepted(accepted) => {
+                    debug!(
+                        "{}: bundle {} accepted for slot {}",
+                        self.name, result.bundle_id, accepted.slot
+                    );
+                    BundleStatus::Pending
+                }
+                BundleOutcome::Processed(processed) => BundleStatus::Landed {
+                    slot: processed.slot,
+                },
+                // Carries no slot; the landing was already recorded when processed.
+                BundleOutcome::Finalized(_) => continue,
+                BundleOutcome::Rejected(rejected) => {
+                    warn!(
+                        "{}: bundle {} rejected: {}",
+                        self.name,
+                        result.bundle_id,
+                        rejection_reason(&rejected)
+                    );
+                    BundleStatus::Failed
+                }
+                BundleOutcome::Dropped(dropped) => {
+                    warn!(
+                        "{}: bundle {} dropped (reason {})",
+                        self.name, result.bundle_id, dropped.reason
+                    );
+                    BundleStatus::Failed
+                }
+            };
+            self.results
+                .lock()
+                .unwrap()
+                .record(result.bundle_id, status);
+        }
+        Ok(())
+    }
+}
+
+fn rejection_reason(rejected: &proto::Rejected) -> String {
+    use proto::rejected::Reason;
+    match &rejected.reason {
+        Some(Reason::SimulationFailure(failure)) => format!(
+            "simulation failed for {}: {}",
+            failure.tx_signature,
+            failure.msg.as_deref().unwrap_or_default()
+        ),
+        Some(Reason::InternalError(reason)) => format!("internal error: {}", reason.msg),
+        Some(Reason::DroppedBundle(reason)) => format!("dropped: {}", reason.msg),
+        None => "auction bid rejected".to_string(),
+    }
+}
+
+fn expires_at(token: &proto::Token) -> i64 {
+    token
+        .expires_at_utc
+        .as_ref()
+        .map(|ts| ts.seconds)
+        .unwrap_or_else(|| unix_now() + TOKEN_REFRESH_MARGIN.as_secs() as i64 * 2)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl TxSender for JitoGrpcTxSender {
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    fn rpc_type(&self) -> RpcType {
+        RpcType::JitoGrpc
+    }
+
+    /// Submits the transaction as a single-transaction bundle.
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        self.inner.check_authenticated()?;
+
+        let data =
+            bincode_serde::encode_to_vec(tx, config::standard()).context("cannot serialize tx")?;
+        let packet = proto::Packet {
+            meta: Some(proto::Meta {
+                size: data.len() as u64,
+            }),
+            data,
+        };
+        let response: proto::SendBundleResponse = self
+            .inner
+            .unary(
+                SEND_BUNDLE,
+                proto::SendBundleRequest {
+                    bundle: Some(proto::Bundle {
+                        packets: vec![packet],
+                    }),
+                },
+                true,
+            )
+            .await
+            .map_err(|e| match e.downcast_ref::<tonic::Status>() {
+                Some(status) => SendError::from_grpc(&self.inner.name, status).into(),
+                None => e.context("SendBundle failed"),
+            })?;
+
+        info!(target: "meteora", "grpc bundle accepted: {}", response.uuid);
+        Ok(TxResult::BundleID(response.uuid))
+    }
+
+    /// Outcome reported by the bundle-result stream, if any arrived yet.
+    async fn get_bundle_status(&self, bundle_id: &str) -> anyhow::Result<Option<BundleStatus>> {
+        let status = self
+            .inner
+            .results
+            .lock()
+            .unwrap()
+            .statuses
+            .get(bundle_id)
+            .copied();
+        Ok(Some(status.unwrap_or(BundleStatus::Pending)))
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        Ok(self.rpc_client.get_block_height().await?)
+    }
+
+    /// Waits up to `AUTH_WAIT` for the background authentication, then for the HTTP/2
+    /// channel to be connected.
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        if self.inner.auth_keypair.is_some() {
+            let mut authenticated = self.inner.authenticated.subscribe();
+            let _ = tokio::time::timeout(AUTH_WAIT, authenticated.wait_for(|ok| *ok)).await;
+        }
+        self.inner.check_authenticated()?;
+        let mut grpc = tonic::client::Grpc::new(self.inner.channel.clone());
+        grpc.ready().await.map_err(|e| SendError::Network {
+            provider: self.inner.name.clone(),
+            message: e.to_string(),
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::message::{Message, VersionedMessage};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Signature;
+    use solana_sdk::system_instruction;
+    use std::convert::Infallible;
+    use std::net::{SocketAddr, TcpListener};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tonic::codegen::{http, http_body, BoxFuture, Context, Poll, Service, StdError};
+    use tonic::server::{Grpc, NamedService, ServerStreamingService, UnaryService};
+    use tonic::Status;
+
+    const CHALLENGE: &str = "challenge-1";
+    const REFRESH_TOKEN: &str = "refresh-1";
+    const LANDED_BUNDLE: &str = "bundle-landed";
+    const FAILED_BUNDLE: &str = "bundle-failed";
+
+    /// What the mock block-engine issued and saw.
+    struct MockState {
+        /// Lifetime of the first access token; a short one makes the client refresh.
+        first_access_ttl: i64,
+        access_token: Mutex<String>,
+        refreshes: AtomicUsize,
+        /// `(path, authorization header)` of every authorized call.
+        authorizations: Mutex<Vec<(&'static str, String)>>,
+    }
+
+    impl MockState {
+        fn authorized(&self, path: &'static str, metadata: &tonic::metadata::MetadataMap) {
+            let header = metadata
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            self.authorizations.lock().unwrap().push((path, header));
+        }
+
+        fn saw(&self, path: &str, header: &str) -> bool {
+            self.authorizations
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(seen_path, seen)| *seen_path == path && seen == header)
+        }
+    }
+
+    fn token(value: &str, ttl_secs: i64) -> proto::Token {
+        proto::Token {
+            value: value.to_string(),
+            expires_at_utc: Some(proto::Timestamp {
+                seconds: unix_now() + ttl_secs,
+                nanos: 0,
+            }),
+        }
+    }
+
+    /// Unary handler from a closure.
+    struct Unary<F>(F);
+
+    impl<Req, Resp, F> UnaryService<Req> for Unary<F>
+    where
+        F: FnMut(tonic::Request<Req>) -> Result<Resp, Status>,
+    {
+        type Response = Resp;
+        type Future = std::future::Ready<Result<tonic::Response<Resp>, Status>>;
+
+        fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+            std::future::ready((self.0)(request).map(tonic::Response::new))
+        }
+    }
+
+    /// Bundle-result stream: one landed and one rejected bundle, then the stream ends so
+    /// the client re-subscribes with its current token.
+    struct BundleResultStream(Arc<MockState>);
+
+    impl ServerStreamingService<proto::SubscribeBundleResultsRequest> for BundleResultStream {
+        type Response = proto::BundleResult;
+        type ResponseStream = futures::stream::BoxStream<'static, Result<Self::Response, Status>>;
+        type Future = std::future::Ready<Result<tonic::Response<Self::ResponseStream>, Status>>;
+
+        fn call(
+            &mut self,
+            request: tonic::Request<proto::SubscribeBundleResultsRequest>,
+        ) -> Self::Future {
+            self.0
+                .authorized(SUBSCRIBE_BUNDLE_RESULTS, request.metadata());
+            let results = vec![
+                Ok(proto::BundleResult {
+                    bundle_id: LANDED_BUNDLE.to_string(),
+                    result: Some(BundleOutcome::Processed(proto::Processed {
+                        validator_identity: String::new(),
+                        slot: 42,
+                        bundle_index: 0,
+                    })),
+                }),
+                Ok(proto::BundleResult {
+                    bundle_id: FAILED_BUNDLE.to_string(),
+                    result: Some(BundleOutcome::Rejected(proto::Rejected {
+                        reason: Some(proto::rejected::Reason::InternalError(
+                            proto::ReasonMessage {
+                                msg: "mock".to_string(),
+                            },
+                        )),
+                    })),
+                }),
+            ];
+            let stream: Self::ResponseStream = Box::pin(futures::stream::iter(results));
+            std::future::ready(Ok(tonic::Response::new(stream)))
+        }
+    }
+
+    /// Block-engine stand-in; `AUTH` selects which of the two gRPC services it is named as.
+    #[derive(Clone)]
+    struct MockBlockEngine<const AUTH: bool>(Arc<MockState>);
+
+    impl NamedService for MockBlockEngine<true> {
+        const NAME: &'static str = "auth.AuthService";
+    }
+
+    impl NamedService for MockBlockEngine<false> {
+        const NAME: &'static str = "searcher.SearcherService";
+    }
+
+    async fn unary<Req, Resp, B>(
+        request: http::Request<B>,
+        handler: impl FnMut(tonic::Request<Req>) -> Result<Resp, Status> + Send + 'static,
+    ) -> http::Response<tonic::body::Body>
+    where
+        Req: prost::Message + Default + Send + 'static,
+        Resp: prost::Message + Send + 'static,
+        B: http_body::Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        Grpc::new(ProstCodec::<Resp, Req>::default())
+            .unary(Unary(handler), request)
+            .await
+    }
+
+    impl<const AUTH: bool, B> Service<http::Request<B>> for MockBlockEngine<AUTH>
+    where
+        B: http_body::Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<B>) -> Self::Future {
+            let state = self.0.clone();
+            Box::pin(async move {
+                let response = match request.uri().path() {
+                    GENERATE_AUTH_CHALLENGE => {
+                        unary(
+                            request,
+                            |_: tonic::Request<proto::GenerateAuthChallengeRequest>| {
+                                Ok(proto::GenerateAuthChallengeResponse {
+                                    challenge: CHALLENGE.to_string(),
+                                })
+                            },
+                        )
+                        .await
+                    }
+                    GENERATE_AUTH_TOKENS => {
+                        unary(
+                            request,
+                            move |request: tonic::Request<proto::GenerateAuthTokensRequest>| {
+                                let request = request.into_inner();
+                                let pubkey = Pubkey::try_from(request.client_pubkey.as_slice())
+                                    .map_err(|_| Status::invalid_argument("bad pubkey"))?;
+                                let signature =
+                                    Signature::try_from(request.signed_challenge.as_slice())
+                                        .map_err(|_| Status::invalid_argument("bad signature"))?;
+                                let expected = format!("{}-{}", pubkey, CHALLENGE);
+                                if request.challenge != expected
+                                    || !signature.verify(pubkey.as_ref(), expected.as_bytes())
+                                {
+                                    return Err(Status::unauthenticated("challenge not signed"));
+                                }
+                                let access = state.access_token.lock().unwrap().clone();
+                                Ok(proto::GenerateAuthTokensResponse {
+                                    access_token: Some(token(&access, state.first_access_ttl)),
+                                    refresh_token: Some(token(REFRESH_TOKEN, 3600)),
+                                })
+                            },
+                        )
+                        .await
+                    }
+                    REFRESH_ACCESS_TOKEN => {
+                        unary(
+                            request,
+                            move |request: tonic::Request<proto::RefreshAccessTokenRequest>| {
+                                if request.get_ref().refresh_token != REFRESH_TOKEN {
+                                    return Err(Status::unauthenticated("unknown refresh token"));
+                                }
+                                let refreshes = state.refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+                                let access = format!("access-{}", refreshes + 1);
+                                *state.access_token.lock().unwrap() = access.clone();
+                                Ok(proto::RefreshAccessTokenResponse {
+                                    access_token: Some(token(&access, 3600)),
+                                })
+                            },
+                        )
+                        .await
+                    }
+                    SEND_BUNDLE => {
+                        unary(
+                            request,
+                            move |request: tonic::Request<proto::SendBundleRequest>| {
+                                state.authorized(SEND_BUNDLE, request.metadata());
+                                let packet = request
+                                    .into_inner()
+                                    .bundle
+                                    .and_then(|bundle| bundle.packets.into_iter().next())
+                                    .ok_or_else(|| Status::invalid_argument("empty bundle"))?;
+                                let (tx, _): (VersionedTransaction, _) =
+                                    bincode_serde::decode_from_slice(
+                                        &packet.data,
+                                        config::standard(),
+                                    )
+                                    .map_err(|_| Status::invalid_argument("bad packet"))?;
+                                Ok(proto::SendBundleResponse {
+                                    uuid: format!("bundle-{}", tx.signatures[0]),
+                                })
+                            },
+                        )
+                        .await
+                    }
+                    SUBSCRIBE_BUNDLE_RESULTS => {
+                        Grpc::new(ProstCodec::<
+                            proto::BundleResult,
+                            proto::SubscribeBundleResultsRequest,
+                        >::default())
+                        .server_streaming(BundleResultStream(state), request)
+                        .await
+                    }
+                    _ => Status::unimplemented("not mocked").into_http(),
+                };
+                Ok(response)
+            })
+        }
+    }
+
+    /// Serves the mock on a free local port; returns its state and URL.
+    async fn start_block_engine(first_access_ttl: i64) -> (Arc<MockState>, String) {
+        let state = Arc::new(MockState {
+            first_access_ttl,
+            access_token: Mutex::new("access-1".to_string()),
+            refreshes: AtomicUsize::new(0),
+            authorizations: Mutex::new(Vec::new()),
+        });
+        let addr: SocketAddr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let server = tonic::transport::Server::builder()
+            .add_service(MockBlockEngine::<true>(state.clone()))
+            .add_service(MockBlockEngine::<false>(state.clone()))
+            .serve(addr);
+        tokio::spawn(server);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        (state, format!("http://{}", addr))
+    }
+
+    fn sender(url: String, auth_keypair: Keypair) -> JitoGrpcTxSender {
+        JitoGrpcTxSender::new(
+            "jito-grpc".to_string(),
+            url,
+            Some(auth_keypair),
+            Arc::new(RpcClient::new("http://127.0.0.1:1".to_string())),
+        )
+        .unwrap()
+    }
+
+    fn signed_transfer() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let message =
+            Message::new_with_blockhash(&[transfer], Some(&payer.pubkey()), &Hash::new_unique());
+        VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap()
+    }
+
+    /// Polls `condition` for up to five seconds.
+    async fn eventually(mut condition: impl FnMut() -> bool) -> bool {
+        for _ in 0..50 {
+            if condition() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn authenticates_and_sends_bundle() {
+        let (state, url) = start_block_engine(3600).await;
+        let sender = sender(url, Keypair::new());
+        sender.warm_up().await.unwrap();
+
+        let tx = signed_transfer();
+        let result = sender.send_transaction(&tx).await.unwrap();
+
+        let expected = format!("bundle-{}", tx.signatures[0]);
+        assert!(matches!(result, TxResult::BundleID(uuid) if uuid == expected));
+        assert!(state.saw(SEND_BUNDLE, "Bearer access-1"));
+    }
+
+    #[tokio::test]
+    async fn records_bundle_results() {
+        let (_state, url) = start_block_engine(3600).await;
+        let sender = sender(url, Keypair::new());
+        sender.warm_up().await.unwrap();
+
+        let landed = eventually(|| {
+            let results = sender.inner.results.lock().unwrap();
+            results.statuses.contains_key(LANDED_BUNDLE)
+                && results.statuses.contains_key(FAILED_BUNDLE)
+        })
+        .await;
+        assert!(landed, "no bundle results received");
+        assert!(matches!(
+            sender.get_bundle_status(LANDED_BUNDLE).await.unwrap(),
+            Some(BundleStatus::Landed { slot: 42 })
+        ));
+        assert!(matches!(
+            sender.get_bundle_status(FAILED_BUNDLE).await.unwrap(),
+            Some(BundleStatus::Failed)
+        ));
+        assert!(matches!(
+            sender.get_bundle_status("unknown").await.unwrap(),
+            Some(BundleStatus::Pending)
+        ));
+    }
+
+    #[tokio::test]
+    async fn refreshes_access_token_before_expiry() {
+        // Expires within the refresh margin, so the refresh is due after the 1 s minimum.
+        let (state, url) = start_block_engine(TOKEN_REFRESH_MARGIN.as_secs() as i64 + 1).await;
+        let sender = sender(url, Keypair::new());
+        sender.warm_up().await.unwrap();
+        let subscribed =
+            eventually(|| state.saw(SUBSCRIBE_BUNDLE_RESULTS, "Bearer access-1")).await;
+        assert!(
+            subscribed,
+            "bundle results not subscribed with the first token"
+        );
+
+        let refreshed = eventually(|| state.refreshes.load(Ordering::SeqCst) > 0).await;
+        assert!(refreshed, "access token was not refreshed");
+
+        // Re-opened subscriptions and new bundles carry the refreshed token.
+        let resubscribed =
+            eventually(|| state.saw(SUBSCRIBE_BUNDLE_RESULTS, "Bearer access-2")).await;
+        assert!(
+            resubscribed,
+            "bundle results not re-subscribed with the new token"
+        );
+        sender.send_transaction(&signed_transfer()).await.unwrap();
+        assert!(state.saw(SEND_BUNDLE, "Bearer access-2"));
+    }
+
+    #[tokio::test]
+    async fn fails_fast_while_unauthenticated() {
+        // Nothing listens here, so the background authentication keeps failing.
+        let sender = sender("http://127.0.0.1:1".to_string(), Keypair::new());
+
+        let started = std::time::Instant::now();
+        let err = sender
+            .send_transaction(&signed_transfer())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SendError>(),
+            Some(SendError::Unavailable { .. })
+        ));
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -1,20 +1,34 @@
 use crate::config::{RpcConfig, RpcType};
 use crate::tx_senders::jito::JitoTxSender;
 use crate::tx_senders::solana_rpc::GenericRpc;
-use crate::tx_senders::transaction::TransactionConfig;
 use async_trait::async_trait;
 use reqwest::Client;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::hash::Hash;
+use solana_sdk::signature::read_keypair_file;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
 use std::sync::Arc;
 use tracing::{info, warn};
 
 pub mod bloxroute;
+pub mod compute_budget;
+pub mod confirmation;
+pub mod connection;
 pub mod constants;
+pub mod error;
+pub mod guard;
 pub mod jito;
+pub mod jito_grpc;
 pub mod nextblock;
+pub mod nonce;
+pub mod priority_fee;
+// Only used by the replay tool.
+#[allow(dead_code)]
+pub mod recording;
+pub mod relay;
+pub mod scoring;
 pub mod solana_rpc;
+pub mod tpu;
 pub mod transaction;
 
 #[derive(Debug, Clone)]
@@ -32,26 +46,48 @@ impl Into<String> for TxResult {
     }
 }
 
+/// Landing state of a bundle as reported by the provider.
+#[derive(Debug, Clone, Copy)]
+pub enum BundleStatus {
+    Pending,
+    Landed { slot: u64 },
+    Failed,
+}
+
 #[async_trait]
 pub trait TxSender: Sync + Send {
     fn name(&self) -> String;
 
-    /// Send a swap transaction targeting Meteora Dynamic AMM.
-    /// `params` contains all accounts, `recent_blockhash` – latest hash.
-    async fn send_meteora_swap(
-        &self,
-        params: &crate::meteora::types::MeteoraSwapParams,
-        recent_blockhash: Hash,
-    ) -> anyhow::Result<TxResult>;
+    /// Endpoint kind, used by the builder for provider-specific instructions (e.g. Jito tip).
+    fn rpc_type(&self) -> RpcType;
+
+    /// Send an already built and signed swap transaction.
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult>;
+
+    /// Rebroadcast of a transaction already sent once; the default sends it again.
+    async fn resend_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        self.send_transaction(tx).await
+    }
 
     /// Get the current block height from the RPC node.
+    #[allow(dead_code)]
     async fn get_block_height(&self) -> anyhow::Result<u64>;
+
+    /// Opens the connection to the endpoint, or keeps an open one alive. Called before the
+    /// bot goes live and then periodically; the default has nothing to warm up.
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Status of a bundle returned by `send_transaction`; `None` if the provider has no lookup.
+    async fn get_bundle_status(&self, _bundle_id: &str) -> anyhow::Result<Option<BundleStatus>> {
+        Ok(None)
+    }
 }
 
 pub fn create_tx_sender(
     name: String,
     rpc_config: RpcConfig,
-    tx_config: TransactionConfig,
     client: Client,
 ) -> Option<Arc<dyn TxSender>> {
     info!("create_tx_sender {:?}", rpc_config.rpc_type);
@@ -59,16 +95,40 @@ pub fn create_tx_sender(
         RpcType::SolanaRpc => Some(Arc::new(GenericRpc::new(
             name,
             rpc_config.url,
-            tx_config,
             RpcType::SolanaRpc,
         ))),
         RpcType::Jito => Some(Arc::new(JitoTxSender::new(
             name,
             rpc_config.url.clone(),
-            tx_config,
             client,
             Arc::new(RpcClient::new(rpc_config.url)),
         ))),
+        RpcType::JitoGrpc => {
+            let auth_keypair = match rpc_config.identity_keypair.as_deref() {
+                Some(path) => match read_keypair_file(path) {
+                    Ok(keypair) => Some(keypair),
+                    Err(e) => {
+                        warn!("Jito gRPC sender '{name}' skipped – cannot read auth keypair: {e}");
+                        return None;
+                    }
+                },
+                None => None,
+            };
+            match jito_grpc::JitoGrpcTxSender::new(
+                name.clone(),
+                rpc_config.url,
+                auth_keypair,
+                Arc::new(RpcClient::new(
+                    "https://api.mainnet-beta.solana.com".to_string(),
+                )),
+            ) {
+                Ok(sender) => Some(Arc::new(sender)),
+                Err(e) => {
+                    warn!("Jito gRPC sender '{name}' skipped – {e:#}");
+                    None
+                }
+            }
+        }
         RpcType::Bloxroute => {
             if rpc_config
                 .auth
@@ -80,7 +140,6 @@ pub fn create_tx_sender(
                     name,
                     rpc_config.url,
                     rpc_config.auth,
-                    tx_config,
                     client,
                     Arc::new(RpcClient::new(
                         "https://api.mainnet-beta.solana.com".to_string(),
@@ -102,7 +161,6 @@ pub fn create_tx_sender(
                     name,
                     rpc_config.url,
                     rpc_config.auth,
-                    tx_config,
                     client,
                     Arc::new(RpcClient::new(
                         "https://api.mainnet-beta.solana.com".to_string(),
@@ -113,5 +171,50 @@ pub fn create_tx_sender(
                 None
             }
         }
+        RpcType::Relay => {
+            let Some(template) = rpc_config.relay else {
+                warn!("Relay sender '{name}' skipped – missing relay template");
+                return None;
+            };
+            match relay::RelayTxSender::new(
+                name.clone(),
+                rpc_config.url,
+                rpc_config.auth,
+                template,
+                client,
+                Arc::new(RpcClient::new(
+                    "https://api.mainnet-beta.solana.com".to_string(),
+                )),
+            ) {
+                Ok(sender) => Some(Arc::new(sender)),
+                Err(e) => {
+                    warn!("Relay sender '{name}' skipped – {e:#}");
+                    None
+                }
+            }
+        }
+        RpcType::Tpu => {
+            let Some(ws_url) = rpc_config.ws_url.filter(|s| !s.is_empty()) else {
+                warn!("TPU sender '{name}' skipped – missing ws_url");
+                return None;
+            };
+            let identity = match rpc_config.identity_keypair.as_deref() {
+                Some(path) => match read_keypair_file(path) {
+                    Ok(keypair) => Some(keypair),
+                    Err(e) => {
+                        warn!("TPU sender '{name}' skipped – cannot read identity keypair: {e}");
+                        return None;
+                    }
+                },
+                None => None,
+            };
+            Some(Arc::new(tpu::TpuTxSender::new(
+                name,
+                rpc_config.url,
+                ws_url,
+                rpc_config.fanout_slots.unwrap_or(tpu::DEFAULT_FANOUT_SLOTS),
+                identity,
+            )))
+        }
     }
 }
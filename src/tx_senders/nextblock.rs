@@ -1,5 +1,6 @@
 use crate::config::RpcType;
-use crate::tx_senders::transaction::{build_meteora_swap_tx, TransactionConfig};
+use crate::tx_senders::connection::ping_http;
+use crate::tx_senders::error::SendError;
 use crate::tx_senders::{TxResult, TxSender};
 
 use anyhow::Context;
@@ -10,7 +11,7 @@ use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{hash::Hash, signature::Signature};
+use solana_sdk::{signature::Signature, transaction::VersionedTransaction};
 use std::{str::FromStr, sync::Arc};
 use tracing::info;
 
@@ -20,7 +21,6 @@ pub struct NextBlockTxSender {
     name: String,
     auth_header: Option<String>,
     client: Client,
-    tx_config: TransactionConfig,
     rpc_client: Arc<RpcClient>,
 }
 
@@ -29,7 +29,6 @@ impl NextBlockTxSender {
         name: String,
         url: String,
         auth_header: Option<String>,
-        tx_config: TransactionConfig,
         client: Client,
         rpc_client: Arc<RpcClient>,
     ) -> Self {
@@ -38,7 +37,6 @@ impl NextBlockTxSender {
             name,
             auth_header,
             client,
-            tx_config,
             rpc_client,
         }
     }
@@ -55,20 +53,13 @@ impl TxSender for NextBlockTxSender {
         self.name.clone()
     }
 
-    async fn send_meteora_swap(
-        &self,
-        params: &crate::meteora::types::MeteoraSwapParams,
-        recent_blockhash: Hash,
-    ) -> anyhow::Result<TxResult> {
-        let tx = build_meteora_swap_tx(
-            &self.tx_config,
-            &RpcType::SolanaRpc,
-            recent_blockhash,
-            params,
-        );
+    fn rpc_type(&self) -> RpcType {
+        RpcType::NextBlock
+    }
 
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
         let cfg = config::standard();
-        let tx_bytes = bincode_serde::encode_to_vec(&tx, cfg).context("cannot serialize tx")?;
+        let tx_bytes = bincode_serde::encode_to_vec(tx, cfg).context("cannot serialize tx")?;
         let tx_base64 = BASE64_STD.encode(tx_bytes);
 
         let body = json!({
@@ -86,11 +77,17 @@ impl TxSender for NextBlockTxSender {
             req = req.header("Authorization", auth);
         }
 
-        let resp = req.send().await?;
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
         let status = resp.status();
-        let text = resp.text().await?;
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
         if !status.is_success() {
-            return Err(anyhow::anyhow!("nextblock submit failed: {}", text));
+            return Err(SendError::from_nextblock(&self.name, status, &text).into());
         }
 
         let sig_resp: Result<SubmitResponse, _> = serde_json::from_str(&text);
@@ -107,4 +104,8 @@ impl TxSender for NextBlockTxSender {
     async fn get_block_height(&self) -> anyhow::Result<u64> {
         Ok(self.rpc_client.get_block_height().await?)
     }
+
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        ping_http(&self.name, &self.client, &self.url).await
+    }
 }
@@ -0,0 +1,225 @@
+use crate::core::blockhash::BlockhashCache;
+
+use anyhow::Context;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account_utils::StateMut;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::v0::Message;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::nonce::state::{State, Versions};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// How often variant signatures are polled while waiting for one to land.
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Durable nonce shared by all per-sender variants of a snipe, so at most one can land.
+pub struct NonceManager {
+    account: Pubkey,
+    rpc_client: Arc<RpcClient>,
+    /// Locked by the snipe signing against the nonce until it has advanced and been re-read.
+    state: Arc<Mutex<NonceState>>,
+    blockhash: BlockhashCache,
+}
+
+#[derive(Default)]
+struct NonceState {
+    /// Last known nonce value; `None` means it has to be fetched.
+    current: Option<Hash>,
+    /// Value the previous snipe signed against, never handed out again.
+    spent: Option<Hash>,
+}
+
+/// Exclusive use of the nonce by one snipe. Other snipes cannot check the nonce out until
+/// the lease is settled or dropped.
+pub struct NonceLease {
+    hash: Hash,
+    state: OwnedMutexGuard<NonceState>,
+}
+
+impl NonceLease {
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Marks the leased value as spent and stores the re-read nonce. A value that has not
+    /// advanced yet (the RPC node lags) is dropped, so the next snipe fetches it again.
+    fn settle(mut self, fetched: anyhow::Result<Hash>) {
+        self.state.spent = Some(self.hash);
+        self.state.current = match fetched {
+            Ok(hash) if hash != self.hash => Some(hash),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("failed to refresh nonce: {:#}", e);
+                None
+            }
+        };
+    }
+}
+
+impl NonceManager {
+    pub fn new(account: Pubkey, http_rpc: String, blockhash: BlockhashCache) -> Self {
+        Self {
+            account,
+            rpc_client: Arc::new(RpcClient::new_with_commitment(
+                http_rpc,
+                CommitmentConfig::confirmed(),
+            )),
+            state: Arc::new(Mutex::new(NonceState::default())),
+            blockhash,
+        }
+    }
+
+    pub fn account(&self) -> Pubkey {
+        self.account
+    }
+
+    /// Leases the nonce for one snipe, fetching its value if it is not known. Fails instead
+    /// of waiting while a previous snipe still holds it, or when the value has not advanced
+    /// since the previous snipe used it.
+    pub async fn checkout(&self) -> anyhow::Result<NonceLease> {
+        let mut state =
+            self.state.clone().try_lock_owned().map_err(|_| {
+                anyhow::anyhow!("nonce {} is held by a previous snipe", self.account)
+            })?;
+        let hash = match state.current {
+            Some(hash) => hash,
+            None => {
+                let hash = self.fetch().await?;
+                if state.spent == Some(hash) {
+                    anyhow::bail!("nonce {} has not advanced yet", self.account);
+                }
+                state.current = Some(hash);
+                hash
+            }
+        };
+        Ok(NonceLease { hash, state })
+    }
+
+    /// Reads the nonce value from the account.
+    async fn fetch(&self) -> anyhow::Result<Hash> {
+        let account = self
+            .rpc_client
+            .get_account(&self.account)
+            .await
+            .context("cannot fetch nonce account")?;
+        let versions: Versions = account.state().context("invalid nonce account data")?;
+        let hash = match versions.state() {
+            State::Initialized(data) => data.blockhash(),
+            State::Uninitialized => {
+                anyhow::bail!("nonce account {} is not initialized", self.account)
+            }
+        };
+        Ok(hash)
+    }
+
+    /// Waits until one of the `(sender, signature)` variants lands and returns it.
+    /// If none lands within `expiry`, the nonce is advanced so no stale variant can land later.
+    /// The lease is released once the advanced nonce has been re-read.
+    pub async fn await_landed_variant(
+        &self,
+        lease: NonceLease,
+        variants: Vec<(String, Signature)>,
+        authority: &Keypair,
+        expiry: Duration,
+    ) -> Option<(String, Signature)> {
+        let signatures: Vec<Signature> = variants.iter().map(|(_, sig)| *sig).collect();
+        let deadline = Instant::now() + expiry;
+
+        let landed = loop {
+            match self.rpc_client.get_signature_statuses(&signatures).await {
+                Ok(response) => {
+                    let hit = response.value.iter().position(|status| status.is_some());
+                    if let Some(i) = hit {
+                        break Some(variants[i].clone());
+                    }
+                }
+                Err(e) => warn!("nonce variant status poll failed: {e}"),
+            }
+            if Instant::now() >= deadline {
+                break None;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        if landed.is_none() {
+            if let Err(e) = self.invalidate(authority).await {
+                warn!("failed to advance nonce after expiry: {:#}", e);
+            }
+        }
+        lease.settle(self.fetch().await);
+        landed
+    }
+
+    /// Advances the nonce, invalidating every transaction signed against the current value.
+    async fn invalidate(&self, authority: &Keypair) -> anyhow::Result<()> {
+        let blockhash = self.blockhash.get_or_fetch(&self.rpc_client).await?;
+        let ix = system_instruction::advance_nonce_account(&self.account, &authority.pubkey());
+        let message = Message::try_compile(&authority.pubkey(), &[ix], &[], blockhash)?;
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[authority])?;
+        let signature = self.rpc_client.send_and_confirm_transaction(&tx).await?;
+        info!("No variant landed, nonce advanced: {signature}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BlockhashConfig;
+
+    /// Nothing listens here, so any fetch fails.
+    const UNREACHABLE_RPC: &str = "http://127.0.0.1:1";
+
+    fn manager(current: Hash) -> NonceManager {
+        let manager = NonceManager::new(
+            Pubkey::new_unique(),
+            UNREACHABLE_RPC.to_string(),
+            BlockhashCache::new(BlockhashConfig::default()),
+        );
+        manager.state.try_lock().unwrap().current = Some(current);
+        manager
+    }
+
+    #[tokio::test]
+    async fn back_to_back_snipes_do_not_share_a_nonce() {
+        let first_hash = Hash::new_unique();
+        let nonce = manager(first_hash);
+
+        let first = nonce.checkout().await.unwrap();
+        assert_eq!(first.hash(), first_hash);
+        assert!(nonce.checkout().await.is_err());
+
+        let advanced = Hash::new_unique();
+        first.settle(Ok(advanced));
+        let second = nonce.checkout().await.unwrap();
+        assert_eq!(second.hash(), advanced);
+    }
+
+    #[tokio::test]
+    async fn spent_nonce_is_not_handed_out_again() {
+        let hash = Hash::new_unique();
+        let nonce = manager(hash);
+
+        // The RPC node still reports the value the first snipe used.
+        nonce.checkout().await.unwrap().settle(Ok(hash));
+        assert!(nonce.checkout().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dropped_lease_releases_the_nonce() {
+        let hash = Hash::new_unique();
+        let nonce = manager(hash);
+
+        drop(nonce.checkout().await.unwrap());
+        assert_eq!(nonce.checkout().await.unwrap().hash(), hash);
+    }
+}
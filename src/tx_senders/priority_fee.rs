@@ -0,0 +1,219 @@
+use crate::config::PriorityFeeConfig;
+use crate::meteora::constants::{swap_indices, METEORA_PROGRAM, SWAP_DISCRIM};
+use futures::future::join_all;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::compute_budget;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Tag byte of `ComputeBudgetInstruction::SetComputeUnitPrice`.
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+/// Pool-side accounts polled for contention fees, most recently seen kept.
+const MAX_TRACKED_ACCOUNTS: usize = 16;
+/// Write-locked pool-side accounts of a Meteora `swap`, by position.
+const SWAP_POOL_ACCOUNTS: [usize; 9] = [
+    swap_indices::POOL,
+    swap_indices::A_VAULT,
+    swap_indices::B_VAULT,
+    swap_indices::A_TOKEN_VAULT,
+    swap_indices::B_TOKEN_VAULT,
+    swap_indices::A_VAULT_LP_MINT,
+    swap_indices::B_VAULT_LP_MINT,
+    swap_indices::A_VAULT_LP,
+    swap_indices::B_VAULT_LP,
+];
+
+/// Estimates the compute-unit price for a snipe from recent prioritization fees.
+pub struct PriorityFeeOracle {
+    config: PriorityFeeConfig,
+    rpc_client: Arc<RpcClient>,
+    /// Static `compute_unit_price`, used when no samples are available.
+    fallback_price: u64,
+    /// Prices seen in competing Meteora transactions, oldest first.
+    observed: Mutex<VecDeque<u64>>,
+    /// Pool-side accounts to poll, from recent snipes and observed swaps; oldest first.
+    tracked: Mutex<VecDeque<Pubkey>>,
+    /// `(slot, fee)` from the last `getRecentPrioritizationFees` poll of each account.
+    rpc_samples: Mutex<HashMap<Pubkey, Vec<(u64, u64)>>>,
+}
+
+impl PriorityFeeOracle {
+    pub fn new(config: PriorityFeeConfig, http_rpc: String, fallback_price: u64) -> Self {
+        Self {
+            observed: Mutex::new(VecDeque::with_capacity(config.observation_window)),
+            config,
+            rpc_client: Arc::new(RpcClient::new(http_rpc)),
+            fallback_price,
+            tracked: Mutex::new(VecDeque::with_capacity(MAX_TRACKED_ACCOUNTS)),
+            rpc_samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Polls `getRecentPrioritizationFees` for the Meteora program and every tracked pool
+    /// account in the background, so snipes never wait on the RPC.
+    pub fn spawn_rpc_sampler(self: &Arc<Self>) {
+        let oracle = self.clone();
+        let interval = Duration::from_millis(self.config.rpc_poll_interval_ms.max(100));
+        tokio::spawn(async move {
+            loop {
+                oracle.sample_rpc().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Adds write-locked pool accounts to the polled set, evicting the least recent ones.
+    pub fn track(&self, accounts: &[Pubkey]) {
+        let mut tracked = self.tracked.lock().unwrap();
+        for account in accounts {
+            tracked.retain(|known| known != account);
+            tracked.push_back(*account);
+        }
+        while tracked.len() > MAX_TRACKED_ACCOUNTS {
+            tracked.pop_front();
+        }
+    }
+
+    /// One query per account: a query for several accounts only returns their combined
+    /// maximum, which could not be reused for another pool sharing some of them.
+    async fn sample_rpc(&self) {
+        let mut keys = vec![METEORA_PROGRAM];
+        keys.extend(self.tracked.lock().unwrap().iter().copied());
+        let timeout = Duration::from_millis(self.config.rpc_timeout_ms);
+        let queries = keys.iter().map(|key| async move {
+            let fees = tokio::time::timeout(
+                timeout,
+                self.rpc_client.get_recent_prioritization_fees(&[*key]),
+            )
+            .await;
+            (*key, fees)
+        });
+
+        let mut samples = HashMap::with_capacity(keys.len());
+        for (key, fees) in join_all(queries).await {
+            match fees {
+                Ok(Ok(fees)) => {
+                    let fees = fees
+                        .iter()
+                        .map(|fee| (fee.slot, fee.prioritization_fee))
+                        .collect();
+                    samples.insert(key, fees);
+                }
+                Ok(Err(e)) => warn!("getRecentPrioritizationFees for {key} failed: {e}"),
+                Err(_) => warn!(
+                    "getRecentPrioritizationFees for {key} timed out after {} ms",
+                    self.config.rpc_timeout_ms
+                ),
+            }
+        }
+        *self.rpc_samples.lock().unwrap() = samples;
+    }
+
+    /// Records the compute-unit price of a transaction seen on the geyser stream, and
+    /// tracks the pool accounts of the swaps in it.
+    pub fn observe(&self, transaction: &VersionedTransaction) {
+        if !self.config.observe_geyser || self.config.observation_window == 0 {
+            return;
+        }
+        self.track(&swap_pool_accounts(transaction));
+        let Some(price) = compute_unit_price_of(transaction) else {
+            return;
+        };
+
+        let mut observed = self.observed.lock().unwrap();
+        if observed.len() >= self.config.observation_window {
+            observed.pop_front();
+        }
+        observed.push_back(price);
+    }
+
+    /// Picks the configured percentile over the latest RPC and geyser samples, clamped to
+    /// the caps. RPC samples are the per-slot maximum over the `accounts` the swap
+    /// write-locks, or the Meteora program's when none of them has been polled yet. Never
+    /// touches the network; the accounts are tracked for the next polls.
+    pub fn estimate(&self, accounts: &[Pubkey]) -> u64 {
+        let mut samples: Vec<u64> = self.observed.lock().unwrap().iter().copied().collect();
+        samples.extend(self.rpc_fees(accounts));
+        self.track(accounts);
+
+        if samples.is_empty() {
+            debug!("no priority-fee samples, using static price");
+            return self.fallback_price;
+        }
+
+        let sample_count = samples.len();
+        let price = percentile(&mut samples, self.config.percentile);
+        debug!(
+            "priority fee p{} over {} samples = {}",
+            self.config.percentile, sample_count, price
+        );
+        price.max(self.config.min_price).min(self.config.max_price)
+    }
+
+    /// Fee per slot to write-lock all polled `accounts` at once.
+    fn rpc_fees(&self, accounts: &[Pubkey]) -> Vec<u64> {
+        let rpc_samples = self.rpc_samples.lock().unwrap();
+        let mut polled = accounts
+            .iter()
+            .filter_map(|account| rpc_samples.get(account))
+            .peekable();
+        if polled.peek().is_none() {
+            return rpc_samples
+                .get(&METEORA_PROGRAM)
+                .map(|fees| fees.iter().map(|(_, fee)| *fee).collect())
+                .unwrap_or_default();
+        }
+        let mut by_slot = BTreeMap::new();
+        for (slot, fee) in polled.flatten() {
+            let max = by_slot.entry(*slot).or_insert(0);
+            *max = (*max).max(*fee);
+        }
+        by_slot.into_values().collect()
+    }
+}
+
+/// Pool-side accounts of the Meteora swaps in a transaction. Only static keys are read;
+/// accounts loaded from lookup tables are skipped.
+fn swap_pool_accounts(transaction: &VersionedTransaction) -> Vec<Pubkey> {
+    let keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| {
+            keys.get(ix.program_id_index as usize) == Some(&METEORA_PROGRAM)
+                && ix.data.starts_with(&SWAP_DISCRIM)
+        })
+        .flat_map(|ix| {
+            SWAP_POOL_ACCOUNTS.iter().filter_map(|&position| {
+                let index = *ix.accounts.get(position)? as usize;
+                keys.get(index).copied()
+            })
+        })
+        .collect()
+}
+
+/// Extracts the `SetComputeUnitPrice` value from a transaction, if present.
+fn compute_unit_price_of(transaction: &VersionedTransaction) -> Option<u64> {
+    let keys = transaction.message.static_account_keys();
+    transaction.message.instructions().iter().find_map(|ix| {
+        let program_id = keys.get(ix.program_id_index as usize)?;
+        if !compute_budget::check_id(program_id)
+            || ix.data.first() != Some(&SET_COMPUTE_UNIT_PRICE_TAG)
+        {
+            return None;
+        }
+        let bytes: [u8; 8] = ix.data.get(1..9)?.try_into().ok()?;
+        Some(u64::from_le_bytes(bytes))
+    })
+}
+
+fn percentile(samples: &mut [u64], pct: u8) -> u64 {
+    samples.sort_unstable();
+    let rank = (samples.len() - 1) * pct.min(100) as usize / 100;
+    samples[rank]
+}
@@ -0,0 +1,58 @@
+use crate::config::RpcType;
+use crate::tx_senders::{TxResult, TxSender};
+
+use async_trait::async_trait;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// A transaction a `RecordingSender` was asked to send.
+#[derive(Clone)]
+pub struct RecordedSend {
+    pub sender: String,
+    pub transaction: VersionedTransaction,
+}
+
+/// Stand-in for a real sender that keeps every transaction instead of sending it, for
+/// replays. Takes the name and type of the sender it replaces, so routing and
+/// per-sender transaction variants stay the same.
+pub struct RecordingSender {
+    name: String,
+    rpc_type: RpcType,
+    sent: Arc<Mutex<Vec<RecordedSend>>>,
+}
+
+impl RecordingSender {
+    pub fn new(name: String, rpc_type: RpcType, sent: Arc<Mutex<Vec<RecordedSend>>>) -> Self {
+        Self {
+            name,
+            rpc_type,
+            sent,
+        }
+    }
+}
+
+#[async_trait]
+impl TxSender for RecordingSender {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn rpc_type(&self) -> RpcType {
+        self.rpc_type.clone()
+    }
+
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        let signature = tx.signatures.first().copied().unwrap_or_default();
+        info!("[REPLAY] {} recorded {}", self.name, signature);
+        self.sent.lock().unwrap().push(RecordedSend {
+            sender: self.name.clone(),
+            transaction: tx.clone(),
+        });
+        Ok(TxResult::Signature(signature))
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,165 @@
+use crate::config::{RelayConfig, RelayEncoding, RpcType};
+use crate::tx_senders::connection::ping_http;
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::{TxResult, TxSender};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::{self, engine::general_purpose::STANDARD as BASE64_STD, Engine as _};
+use bincode::{config, serde as bincode_serde};
+use reqwest::{Client, Method};
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::bs58;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+/// Sender for HTTP relays whose request shape is fully described in `config.yaml`.
+pub struct RelayTxSender {
+    url: String,
+    name: String,
+    auth: String,
+    method: Method,
+    template: RelayConfig,
+    client: Client,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RelayTxSender {
+    pub fn new(
+        name: String,
+        url: String,
+        auth: Option<String>,
+        template: RelayConfig,
+        client: Client,
+        rpc_client: Arc<RpcClient>,
+    ) -> anyhow::Result<Self> {
+        let method = Method::from_bytes(template.method.to_uppercase().as_bytes())
+            .with_context(|| format!("invalid relay method '{}'", template.method))?;
+        Ok(Self {
+            url,
+            name,
+            auth: auth.unwrap_or_default(),
+            method,
+            template,
+            client,
+            rpc_client,
+        })
+    }
+
+    /// Substitutes the placeholders; with `json` the auth value is escaped for use inside
+    /// a JSON string (the transaction encodings need no escaping).
+    fn render(&self, template: &str, tx_bytes: &[u8], json: bool) -> String {
+        let auth = if json {
+            let quoted = Value::String(self.auth.clone()).to_string();
+            quoted[1..quoted.len() - 1].to_string()
+        } else {
+            self.auth.clone()
+        };
+        let mut rendered = template.replace("{auth}", &auth);
+        if rendered.contains("{tx_base64}") {
+            rendered = rendered.replace("{tx_base64}", &BASE64_STD.encode(tx_bytes));
+        }
+        if rendered.contains("{tx_base58}") {
+            rendered = rendered.replace("{tx_base58}", &bs58::encode(tx_bytes).into_string());
+        }
+        rendered
+    }
+}
+
+/// Follows a dot-separated path (object keys or array indices) through a JSON value.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        })
+}
+
+#[async_trait]
+impl TxSender for RelayTxSender {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn rpc_type(&self) -> RpcType {
+        RpcType::Relay
+    }
+
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        let cfg = config::standard();
+        let tx_bytes = bincode_serde::encode_to_vec(tx, cfg).context("cannot serialize tx")?;
+
+        let (content_type, body) = match self.template.encoding {
+            RelayEncoding::Json => (
+                "application/json",
+                self.render(&self.template.body, &tx_bytes, true)
+                    .into_bytes(),
+            ),
+            RelayEncoding::Text => (
+                "text/plain",
+                self.render(&self.template.body, &tx_bytes, false)
+                    .into_bytes(),
+            ),
+            RelayEncoding::Binary => ("application/octet-stream", tx_bytes.clone()),
+        };
+
+        let mut req = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .body(body);
+        // A configured Content-Type replaces the one implied by the encoding.
+        let has_content_type = self
+            .template
+            .headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case("content-type"));
+        if !has_content_type {
+            req = req.header("Content-Type", content_type);
+        }
+        for (key, value) in &self.template.headers {
+            req = req.header(key, self.render(value, &tx_bytes, false));
+        }
+
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| SendError::from_reqwest(&self.name, e))?;
+        if !status.is_success() {
+            return Err(SendError::from_http(&self.name, status, &text).into());
+        }
+
+        let signature = match &self.template.signature_path {
+            Some(path) => {
+                let json: Value = serde_json::from_str(&text).with_context(|| {
+                    format!("{} returned non-JSON response: {}", self.name, text)
+                })?;
+                let value = lookup(&json, path)
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("no signature at '{}' in: {}", path, text))?;
+                Signature::from_str(value)?
+            }
+            None => tx.signatures[0],
+        };
+
+        info!(target: "meteora", "{} tx accepted: {signature}", self.name);
+        Ok(TxResult::Signature(signature))
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        Ok(self.rpc_client.get_block_height().await?)
+    }
+
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        ping_http(&self.name, &self.client, &self.url).await
+    }
+}
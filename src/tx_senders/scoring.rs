@@ -0,0 +1,176 @@
+use crate::tx_senders::solana_rpc::TxMetrics;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Weight of the newest sample in the latency and slot-delta averages.
+const EWMA_ALPHA: f64 = 0.2;
+/// Changed statistics are written to `stats_file` at most this often.
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Historical performance of one sender.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SenderStats {
+    /// Send attempts (broadcasts, not resends).
+    pub attempts: u64,
+    /// Attempts the sender accepted.
+    pub accepted: u64,
+    /// Accepted transactions whose outcome is known.
+    pub settled: u64,
+    /// Settled transactions that landed successfully.
+    pub landed: u64,
+    /// Average response time of accepted sends, in milliseconds.
+    pub avg_latency_ms: f64,
+    /// Average slots between sending and landing.
+    pub avg_slot_delta: f64,
+}
+
+impl SenderStats {
+    /// Higher is better. Rates are smoothed so senders without history start near 0.25
+    /// instead of being starved.
+    pub fn score(&self) -> f64 {
+        let acceptance = (self.accepted as f64 + 1.0) / (self.attempts as f64 + 2.0);
+        let landing = (self.landed as f64 + 1.0) / (self.settled as f64 + 2.0);
+        acceptance * landing
+            / (1.0 + self.avg_slot_delta / 4.0)
+            / (1.0 + self.avg_latency_ms / 200.0)
+    }
+}
+
+fn ewma(current: f64, sample: f64, count: u64) -> f64 {
+    if count <= 1 {
+        sample
+    } else {
+        current + EWMA_ALPHA * (sample - current)
+    }
+}
+
+/// Per-sender statistics, persisted to `stats_file` so routing survives restarts.
+pub struct SenderScores {
+    stats: RwLock<HashMap<String, SenderStats>>,
+    /// Empty disables persistence.
+    stats_file: String,
+    /// Statistics changed since the last save.
+    dirty: AtomicBool,
+}
+
+impl SenderScores {
+    /// Loads previous statistics from `stats_file`, if it exists.
+    pub fn load(stats_file: String) -> Self {
+        let stats = if stats_file.is_empty() {
+            HashMap::new()
+        } else {
+            match fs::read_to_string(&stats_file) {
+                Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                    warn!("ignoring unreadable sender stats {}: {}", stats_file, e);
+                    HashMap::new()
+                }),
+                Err(_) => HashMap::new(),
+            }
+        };
+        if !stats.is_empty() {
+            info!(
+                "Loaded stats for {} senders from {}",
+                stats.len(),
+                stats_file
+            );
+        }
+        Self {
+            stats: RwLock::new(stats),
+            stats_file,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Writes changed statistics to `stats_file` every `SAVE_INTERVAL`, off the async
+    /// workers.
+    pub fn spawn_persistence(self: &Arc<Self>) {
+        if self.stats_file.is_empty() {
+            return;
+        }
+        let scores = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(SAVE_INTERVAL).await;
+                if !scores.dirty.swap(false, Ordering::Relaxed) {
+                    continue;
+                }
+                let saving = scores.clone();
+                if let Err(e) = tokio::task::spawn_blocking(move || saving.save()).await {
+                    warn!("sender stats save panicked: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Writes statistics changed since the last periodic save; called on shutdown.
+    // Only called by the bot itself, which the tool binaries do not include.
+    #[allow(dead_code)]
+    pub fn flush(&self) {
+        if self.dirty.swap(false, Ordering::Relaxed) {
+            self.save();
+        }
+    }
+
+    /// Records the immediate result of a broadcast.
+    pub fn record_send(&self, sender: &str, accepted: bool, latency_ms: u64) {
+        let mut stats = self.stats.write().unwrap();
+        let entry = stats.entry(sender.to_string()).or_default();
+        entry.attempts += 1;
+        if accepted {
+            entry.accepted += 1;
+            entry.avg_latency_ms = ewma(entry.avg_latency_ms, latency_ms as f64, entry.accepted);
+        }
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Records the landing outcome reported by the confirmation tracker. Outcomes shared
+    /// by every sender of the same signature say nothing about any one of them and are
+    /// ignored.
+    pub fn record_outcome(&self, metrics: &TxMetrics) {
+        if !metrics.attributable {
+            return;
+        }
+        let mut stats = self.stats.write().unwrap();
+        let entry = stats.entry(metrics.rpc_name.clone()).or_default();
+        entry.settled += 1;
+        if metrics.success {
+            entry.landed += 1;
+            if let Some(delta) = metrics.slot_latency {
+                entry.avg_slot_delta = ewma(entry.avg_slot_delta, delta as f64, entry.landed);
+            }
+        }
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn score(&self, sender: &str) -> f64 {
+        self.stats
+            .read()
+            .unwrap()
+            .get(sender)
+            .cloned()
+            .unwrap_or_default()
+            .score()
+    }
+
+    fn save(&self) {
+        if self.stats_file.is_empty() {
+            return;
+        }
+        let json = match serde_json::to_string_pretty(&*self.stats.read().unwrap()) {
+            Ok(json) => json,
+            Err(e) => {
+                warn!("cannot serialize sender stats: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = fs::write(&self.stats_file, json) {
+            warn!("cannot write sender stats to {}: {}", self.stats_file, e);
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use crate::config::RpcType;
-use crate::tx_senders::transaction::TransactionConfig;
+use crate::tx_senders::error::SendError;
 use crate::tx_senders::{TxResult, TxSender};
 use async_trait::async_trait;
 use serde::Serialize;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_client::rpc_config::RpcSendTransactionConfig;
-use solana_sdk::hash::Hash;
+use solana_sdk::transaction::VersionedTransaction;
 use solana_transaction_status::UiTransactionEncoding;
 use std::sync::Arc;
 
@@ -13,7 +13,6 @@ use std::sync::Arc;
 pub struct GenericRpc {
     pub name: String,
     pub http_rpc: Arc<RpcClient>,
-    tx_config: TransactionConfig,
     rpc_type: RpcType,
 }
 
@@ -21,21 +20,23 @@ pub struct GenericRpc {
 pub struct TxMetrics {
     pub rpc_name: String,
     pub signature: String,
+    pub bundle_id: Option<String>,
     pub index: u32,
     pub success: bool,
     pub slot_sent: u64,
     pub slot_landed: Option<u64>,
     pub slot_latency: Option<u64>,
     pub elapsed: Option<u64>, // in milliseconds
+    /// Whether the outcome belongs to this sender alone (nonce variant or bundle).
+    pub attributable: bool,
 }
 
 impl GenericRpc {
-    pub fn new(name: String, url: String, config: TransactionConfig, rpc_type: RpcType) -> Self {
+    pub fn new(name: String, url: String, rpc_type: RpcType) -> Self {
         let http_rpc = Arc::new(RpcClient::new(url));
         GenericRpc {
             name,
             http_rpc,
-            tx_config: config,
             rpc_type,
         }
     }
@@ -47,21 +48,15 @@ impl TxSender for GenericRpc {
         self.name.clone()
     }
 
-    async fn send_meteora_swap(
-        &self,
-        params: &crate::meteora::types::MeteoraSwapParams,
-        recent_blockhash: Hash,
-    ) -> anyhow::Result<TxResult> {
-        let tx = crate::tx_senders::transaction::build_meteora_swap_tx(
-            &self.tx_config,
-            &self.rpc_type,
-            recent_blockhash,
-            params,
-        );
+    fn rpc_type(&self) -> RpcType {
+        self.rpc_type.clone()
+    }
+
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
         let sig = self
             .http_rpc
             .send_transaction_with_config(
-                &tx,
+                tx,
                 RpcSendTransactionConfig {
                     skip_preflight: true,
                     preflight_commitment: None,
@@ -70,11 +65,20 @@ impl TxSender for GenericRpc {
                     min_context_slot: None,
                 },
             )
-            .await?;
+            .await
+            .map_err(|e| SendError::from_rpc(&self.name, e))?;
         Ok(TxResult::Signature(sig))
     }
 
     async fn get_block_height(&self) -> anyhow::Result<u64> {
         Ok(self.http_rpc.get_block_height().await?)
     }
+
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        self.http_rpc
+            .get_health()
+            .await
+            .map_err(|e| SendError::from_rpc(&self.name, e))?;
+        Ok(())
+    }
 }
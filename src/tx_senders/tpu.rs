@@ -0,0 +1,246 @@
+use crate::config::RpcType;
+use crate::tx_senders::error::SendError;
+use crate::tx_senders::{TxResult, TxSender};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use bincode::config;
+use bincode::serde as bincode_serde;
+use futures::future::join_all;
+use solana_client::connection_cache::{ConnectionCache, Protocol};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::nonblocking::tpu_client::{LeaderTpuService, TpuSenderError};
+use solana_client::nonblocking::tpu_connection::TpuConnection;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::VersionedTransaction;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+/// QUIC connections kept per leader.
+const CONNECTION_POOL_SIZE: usize = 4;
+/// Upcoming slots whose leaders receive the transaction when `fanout_slots` is not set.
+pub const DEFAULT_FANOUT_SLOTS: u64 = 8;
+
+/// Sends the serialized transaction over QUIC straight to the TPU ports of the current
+/// and upcoming leaders, skipping any relay.
+pub struct TpuTxSender {
+    name: String,
+    rpc_client: Arc<RpcClient>,
+    ws_rpc: String,
+    fanout_slots: u64,
+    connection_cache: Arc<ConnectionCache>,
+    /// Leader schedule and cluster contact info; started in the background on creation
+    /// and retried on send if that failed.
+    leaders: Arc<OnceCell<LeaderTpuService>>,
+}
+
+impl TpuTxSender {
+    /// `identity` is presented in the QUIC client certificate so a staked validator
+    /// identity gets stake-weighted QoS; without it the connection is unstaked.
+    pub fn new(
+        name: String,
+        http_rpc: String,
+        ws_rpc: String,
+        fanout_slots: u64,
+        identity: Option<Keypair>,
+    ) -> Self {
+        let connection_cache = match &identity {
+            Some(identity) => ConnectionCache::new_with_client_options(
+                "tpu-sender",
+                CONNECTION_POOL_SIZE,
+                None,
+                Some((identity, IpAddr::V4(Ipv4Addr::UNSPECIFIED))),
+                None,
+            ),
+            None => ConnectionCache::new_quic("tpu-sender", CONNECTION_POOL_SIZE),
+        };
+        let rpc_client = Arc::new(RpcClient::new(http_rpc));
+        let leaders = Arc::new(OnceCell::new());
+
+        let sender = Self {
+            name,
+            rpc_client,
+            ws_rpc,
+            fanout_slots,
+            connection_cache: Arc::new(connection_cache),
+            leaders,
+        };
+        sender.spawn_leader_service();
+        sender
+    }
+
+    /// Starts tracking the leader schedule right away so the first snipe does not pay for it.
+    fn spawn_leader_service(&self) {
+        let leaders = self.leaders.clone();
+        let rpc_client = self.rpc_client.clone();
+        let ws_rpc = self.ws_rpc.clone();
+        let name = self.name.clone();
+        tokio::spawn(async move {
+            match leaders
+                .get_or_try_init(|| start_leader_service(rpc_client, &ws_rpc))
+                .await
+            {
+                Ok(_) => info!("{name}: leader schedule tracking started"),
+                Err(e) => warn!("{name}: leader schedule tracking failed: {e}"),
+            }
+        });
+    }
+}
+
+async fn start_leader_service(
+    rpc_client: Arc<RpcClient>,
+    ws_rpc: &str,
+) -> Result<LeaderTpuService, TpuSenderError> {
+    LeaderTpuService::new(
+        rpc_client,
+        ws_rpc,
+        Protocol::QUIC,
+        Arc::new(AtomicBool::new(false)),
+    )
+    .await
+}
+
+#[async_trait]
+impl TxSender for TpuTxSender {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn rpc_type(&self) -> RpcType {
+        RpcType::Tpu
+    }
+
+    /// Sends the wire transaction to every leader TPU in the fanout; succeeds if any accepted it.
+    async fn send_transaction(&self, tx: &VersionedTransaction) -> anyhow::Result<TxResult> {
+        let leaders = self
+            .leaders
+            .get_or_try_init(|| start_leader_service(self.rpc_client.clone(), &self.ws_rpc))
+            .await
+            .context("leader schedule unavailable")?;
+        let sockets = leaders.leader_tpu_sockets(self.fanout_slots);
+        if sockets.is_empty() {
+            anyhow::bail!("no leader TPU sockets known");
+        }
+
+        let wire =
+            bincode_serde::encode_to_vec(tx, config::standard()).context("cannot serialize tx")?;
+        let results = join_all(sockets.iter().map(|addr| {
+            let connection = self.connection_cache.get_nonblocking_connection(addr);
+            let wire = &wire;
+            async move { connection.send_data(wire).await }
+        }))
+        .await;
+
+        let mut delivered = 0;
+        let mut last_error = None;
+        for (addr, result) in sockets.iter().zip(results) {
+            match result {
+                Ok(()) => delivered += 1,
+                Err(e) => {
+                    debug!("{}: TPU {} rejected tx: {}", self.name, addr, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if delivered == 0 {
+            return Err(SendError::Network {
+                provider: self.name.clone(),
+                message: format!(
+                    "no leader accepted the tx: {}",
+                    last_error.map(|e| e.to_string()).unwrap_or_default()
+                ),
+            }
+            .into());
+        }
+        debug!(
+            "{}: tx sent to {}/{} leaders",
+            self.name,
+            delivered,
+            sockets.len()
+        );
+
+        Ok(TxResult::Signature(tx.signatures[0]))
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        Ok(self.rpc_client.get_block_height().await?)
+    }
+
+    /// Ready once the leader schedule is tracked; QUIC connections open on first use.
+    async fn warm_up(&self) -> anyhow::Result<()> {
+        self.leaders
+            .get_or_try_init(|| start_leader_service(self.rpc_client.clone(), &self.ws_rpc))
+            .await
+            .context("leader schedule unavailable")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_sdk::message::{Message, VersionedMessage};
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Signature, Signer};
+    use solana_sdk::system_instruction;
+    use std::time::Duration;
+
+    const LOCAL_RPC: &str = "http://127.0.0.1:8899";
+    const LOCAL_WS: &str = "ws://127.0.0.1:8900";
+
+    async fn wait_landed(rpc: &RpcClient, signature: &Signature) -> bool {
+        for _ in 0..60 {
+            if let Ok(Some(status)) = rpc.get_signature_status(signature).await {
+                return status.is_ok();
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        false
+    }
+
+    /// Start `solana-test-validator --reset` first, then run
+    /// `cargo test -- --ignored tpu_sender_lands_on_test_validator`.
+    #[tokio::test]
+    #[ignore = "needs a local solana-test-validator"]
+    async fn tpu_sender_lands_on_test_validator() {
+        let rpc =
+            RpcClient::new_with_commitment(LOCAL_RPC.to_string(), CommitmentConfig::confirmed());
+        let payer = Keypair::new();
+        let airdrop = rpc
+            .request_airdrop(&payer.pubkey(), LAMPORTS_PER_SOL)
+            .await
+            .expect("airdrop request failed");
+        assert!(wait_landed(&rpc, &airdrop).await, "airdrop did not land");
+
+        let sender = TpuTxSender::new(
+            "tpu".to_string(),
+            LOCAL_RPC.to_string(),
+            LOCAL_WS.to_string(),
+            DEFAULT_FANOUT_SLOTS,
+            None,
+        );
+        sender.warm_up().await.expect("leader schedule unavailable");
+
+        let blockhash = rpc.get_latest_blockhash().await.unwrap();
+        let transfer =
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        let message = Message::new_with_blockhash(&[transfer], Some(&payer.pubkey()), &blockhash);
+        let tx =
+            VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap();
+
+        let result = sender
+            .send_transaction(&tx)
+            .await
+            .expect("no leader accepted the tx");
+        assert!(matches!(result, TxResult::Signature(signature) if signature == tx.signatures[0]));
+        assert!(
+            wait_landed(&rpc, &tx.signatures[0]).await,
+            "transaction sent over QUIC did not land"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::{PingThingsArgs, RpcType};
+use crate::config::{PingThingsArgs, RpcType, WsolMode};
 use crate::meteora::constants::METEORA_PROGRAM_ID;
 use crate::meteora::types::{MeteoraSwapParams, TradeDirection};
 use crate::tx_senders::constants::JITO_TIP_ADDR;
@@ -24,9 +24,17 @@ pub struct TransactionConfig {
     pub keypair: Arc<Keypair>,
     pub compute_unit_limit: u32,
     pub compute_unit_price: u64,
+    /// `SetLoadedAccountsDataSizeLimit` in bytes; 0 omits the instruction.
+    pub loaded_accounts_data_size_limit: u32,
     pub tip: u64,
     pub buy_amount: u64,
     pub min_amount_out: u64,
+    /// Swap from a pre-funded WSOL account instead of wrapping/unwrapping per buy.
+    pub persistent_wsol: bool,
+    /// Durable nonce account; when set, `recent_blockhash` must be its nonce value.
+    pub nonce_account: Option<Pubkey>,
+    /// Tip recipient override; when set the tip is paid regardless of sender type.
+    pub tip_account: Option<Pubkey>,
 }
 
 // Custom Debug implementation to redact sensitive keypair data
@@ -36,9 +44,16 @@ impl fmt::Debug for TransactionConfig {
             .field("keypair_pubkey", &self.keypair.pubkey())
             .field("compute_unit_limit", &self.compute_unit_limit)
             .field("compute_unit_price", &self.compute_unit_price)
+            .field(
+                "loaded_accounts_data_size_limit",
+                &self.loaded_accounts_data_size_limit,
+            )
             .field("tip", &self.tip)
             .field("buy_amount", &self.buy_amount)
             .field("min_amount_out", &self.min_amount_out)
+            .field("persistent_wsol", &self.persistent_wsol)
+            .field("nonce_account", &self.nonce_account)
+            .field("tip_account", &self.tip_account)
             .finish()
     }
 }
@@ -55,9 +70,13 @@ impl From<PingThingsArgs> for TransactionConfig {
             keypair: Arc::new(keypair),
             compute_unit_limit: args.compute_unit_limit,
             compute_unit_price: args.compute_unit_price,
+            loaded_accounts_data_size_limit: args.compute_budget.loaded_accounts_data_size_limit,
             tip: tip,
             buy_amount: buy_amount,
             min_amount_out: min_amount_out,
+            persistent_wsol: args.wsol.mode == WsolMode::Persistent,
+            nonce_account: None,
+            tip_account: None,
         }
     }
 }
@@ -70,6 +89,14 @@ pub fn build_meteora_swap_tx(
 ) -> VersionedTransaction {
     let mut instructions: Vec<Instruction> = Vec::new();
 
+    // Durable nonce transactions must advance the nonce in their first instruction.
+    if let Some(nonce_account) = &tx_config.nonce_account {
+        instructions.push(system_instruction::advance_nonce_account(
+            nonce_account,
+            &tx_config.keypair.pubkey(),
+        ));
+    }
+
     // Priority fee instructions
     if tx_config.compute_unit_limit > 0 {
         instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
@@ -81,49 +108,37 @@ pub fn build_meteora_swap_tx(
             tx_config.compute_unit_price,
         ));
     }
-
-    // Optional Jito tip
-    if tx_config.tip > 0 {
-        if let RpcType::Jito = rpc_type {
-            instructions.push(system_instruction::transfer(
-                &tx_config.keypair.pubkey(),
-                &Pubkey::from_str(JITO_TIP_ADDR).unwrap(),
-                tx_config.tip,
-            ));
-        }
+    // Default is 64 MiB; a tighter limit lowers the scheduling cost of the transaction.
+    if tx_config.loaded_accounts_data_size_limit > 0 {
+        instructions.push(
+            ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(
+                tx_config.loaded_accounts_data_size_limit,
+            ),
+        );
     }
 
-    // Pre-compute user WSOL ATA for later transfer/sync instructions.
-    let user_source_wsol = spl_associated_token_account::get_associated_token_address(
-        &tx_config.keypair.pubkey(),
-        &Pubkey::from_str(crate::meteora::constants::WSOL_MINT).unwrap(),
-    );
-
-    // 1) Create WSOL ATA (idempotent)
-    let create_src_ata_ix = create_associated_token_account_idempotent(
-        &tx_config.keypair.pubkey(),
-        &tx_config.keypair.pubkey(),
-        &Pubkey::from_str(crate::meteora::constants::WSOL_MINT).unwrap(),
-        &Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap(),
-    );
-    instructions.push(create_src_ata_ix);
+    // Optional tip: always for Jito, for other senders only with an explicit recipient.
+    if tx_config.tip > 0
+        && (matches!(rpc_type, RpcType::Jito | RpcType::JitoGrpc)
+            || tx_config.tip_account.is_some())
+    {
+        let tip_account = tx_config
+            .tip_account
+            .unwrap_or_else(|| Pubkey::from_str(JITO_TIP_ADDR).unwrap());
+        instructions.push(system_instruction::transfer(
+            &tx_config.keypair.pubkey(),
+            &tip_account,
+            tx_config.tip,
+        ));
+    }
 
-    // 2) Transfer the SOL we intend to swap into the WSOL ATA.
-    let transfer_lamports_ix = system_instruction::transfer(
-        &tx_config.keypair.pubkey(),
-        &user_source_wsol,
-        tx_config.buy_amount,
-    );
-    instructions.push(transfer_lamports_ix);
-
-    // 3) Sync native account to turn lamports into WSOL balance.
-    //    Data layout for SyncNative: command index 17 (u8) + three bytes padding (u8) per Token program spec.
-    let sync_native_ix = Instruction {
-        program_id: Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap(),
-        accounts: vec![AccountMeta::new(user_source_wsol, false)],
-        data: vec![17, 0, 0, 0],
-    };
-    instructions.push(sync_native_ix);
+    // 1-3) Wrap the SOL we intend to swap, unless a pre-funded WSOL account is kept.
+    if !tx_config.persistent_wsol {
+        instructions.extend(wrap_sol_instructions(
+            &tx_config.keypair.pubkey(),
+            tx_config.buy_amount,
+        ));
+    }
 
     // 4) Ensure user destination ATA exists (must be ready before swap to receive tokens).
     let create_dst_ata_ix = create_associated_token_account_idempotent(
@@ -176,15 +191,9 @@ pub fn build_meteora_swap_tx(
     instructions.push(swap_ix);
 
     // 6) Close empty WSOL account back to payer to reclaim rent.
-    let close_wsol_ix = token_instruction::close_account(
-        &Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap(),
-        &user_source_wsol,
-        &tx_config.keypair.pubkey(),
-        &tx_config.keypair.pubkey(),
-        &[],
-    )
-    .unwrap();
-    instructions.push(close_wsol_ix);
+    if !tx_config.persistent_wsol {
+        instructions.push(close_wsol_instruction(&tx_config.keypair.pubkey()));
+    }
 
     let message_v0 = Message::try_compile(
         &tx_config.keypair.pubkey(),
@@ -197,3 +206,81 @@ pub fn build_meteora_swap_tx(
     let versioned_message = VersionedMessage::V0(message_v0);
     VersionedTransaction::try_new(versioned_message, &[&tx_config.keypair]).unwrap()
 }
+
+/// Instructions that move `lamports` of SOL into the owner's WSOL ATA, creating it if needed.
+pub fn wrap_sol_instructions(owner: &Pubkey, lamports: u64) -> Vec<Instruction> {
+    let wsol_mint = Pubkey::from_str(crate::meteora::constants::WSOL_MINT).unwrap();
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap();
+    let user_source_wsol =
+        spl_associated_token_account::get_associated_token_address(owner, &wsol_mint);
+
+    vec![
+        // 1) Create WSOL ATA (idempotent)
+        create_associated_token_account_idempotent(owner, owner, &wsol_mint, &token_program),
+        // 2) Transfer the SOL into the WSOL ATA.
+        system_instruction::transfer(owner, &user_source_wsol, lamports),
+        // 3) Sync native account to turn lamports into WSOL balance.
+        //    Data layout for SyncNative: command index 17 (u8) + three bytes padding (u8) per Token program spec.
+        Instruction {
+            program_id: token_program,
+            accounts: vec![AccountMeta::new(user_source_wsol, false)],
+            data: vec![17, 0, 0, 0],
+        },
+    ]
+}
+
+/// Size of an SPL token account.
+pub const TOKEN_ACCOUNT_LEN: u64 = 165;
+
+/// Unwraps `lamports` of the owner's WSOL while the ATA stays open: the amount is moved
+/// into the fresh token account `temporary` (funded with `rent`, signs the transaction)
+/// and that account is closed to the owner.
+pub fn unwrap_wsol_instructions(
+    owner: &Pubkey,
+    temporary: &Pubkey,
+    lamports: u64,
+    rent: u64,
+) -> Vec<Instruction> {
+    let wsol_mint = Pubkey::from_str(crate::meteora::constants::WSOL_MINT).unwrap();
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap();
+    let user_source_wsol =
+        spl_associated_token_account::get_associated_token_address(owner, &wsol_mint);
+
+    vec![
+        system_instruction::create_account(
+            owner,
+            temporary,
+            rent,
+            TOKEN_ACCOUNT_LEN,
+            &token_program,
+        ),
+        token_instruction::initialize_account3(&token_program, temporary, &wsol_mint, owner)
+            .unwrap(),
+        token_instruction::transfer(
+            &token_program,
+            &user_source_wsol,
+            temporary,
+            owner,
+            &[],
+            lamports,
+        )
+        .unwrap(),
+        token_instruction::close_account(&token_program, temporary, owner, owner, &[]).unwrap(),
+    ]
+}
+
+/// Closes the owner's WSOL ATA, unwrapping its whole balance back to SOL.
+pub fn close_wsol_instruction(owner: &Pubkey) -> Instruction {
+    let user_source_wsol = spl_associated_token_account::get_associated_token_address(
+        owner,
+        &Pubkey::from_str(crate::meteora::constants::WSOL_MINT).unwrap(),
+    );
+    token_instruction::close_account(
+        &Pubkey::from_str(TOKEN_PROGRAM_ADDR).unwrap(),
+        &user_source_wsol,
+        owner,
+        owner,
+        &[],
+    )
+    .unwrap()
+}
@@ -0,0 +1,149 @@
+use crate::bench::SnipesInFlight;
+use crate::config::WsolConfig;
+use crate::core::blockhash::BlockhashCache;
+use crate::meteora::constants::WSOL_MINT;
+use crate::tx_senders::transaction::{
+    unwrap_wsol_instructions, wrap_sol_instructions, TransactionConfig, TOKEN_ACCOUNT_LEN,
+};
+
+use anyhow::Context;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::v0::Message;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Byte range of `amount` in an SPL token account (after mint and owner).
+const TOKEN_ACCOUNT_AMOUNT_RANGE: std::ops::Range<usize> = 64..72;
+
+/// Background task keeping the persistent WSOL account between `min_balance` and `max_balance`.
+pub struct WsolMaintainer {
+    config: WsolConfig,
+    rpc_client: Arc<RpcClient>,
+    keypair: Arc<Keypair>,
+    wsol_account: Pubkey,
+    blockhash: BlockhashCache,
+    snipes_in_flight: SnipesInFlight,
+}
+
+impl WsolMaintainer {
+    pub fn new(
+        config: WsolConfig,
+        http_rpc: String,
+        tx_config: &TransactionConfig,
+        blockhash: BlockhashCache,
+        snipes_in_flight: SnipesInFlight,
+    ) -> Self {
+        let wsol_account = spl_associated_token_account::get_associated_token_address(
+            &tx_config.keypair.pubkey(),
+            &Pubkey::from_str(WSOL_MINT).unwrap(),
+        );
+        Self {
+            config,
+            rpc_client: Arc::new(RpcClient::new_with_commitment(
+                http_rpc,
+                CommitmentConfig::confirmed(),
+            )),
+            keypair: tx_config.keypair.clone(),
+            wsol_account,
+            blockhash,
+            snipes_in_flight,
+        }
+    }
+
+    /// Checks the balance every `check_interval_secs` and wraps or unwraps as needed.
+    pub async fn run(self) {
+        info!(
+            "WSOL maintenance started for {} (target {} SOL)",
+            self.wsol_account, self.config.target_balance
+        );
+        let interval = Duration::from_secs(self.config.check_interval_secs.max(1));
+        loop {
+            if let Err(e) = self.maintain().await {
+                warn!("WSOL maintenance failed: {:#}", e);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn maintain(&self) -> anyhow::Result<()> {
+        // A pending snipe may still spend the balance read here.
+        if self.snipes_in_flight.any() {
+            debug!("WSOL maintenance skipped, snipe in flight");
+            return Ok(());
+        }
+        let balance = self.balance().await?;
+        let target = (self.config.target_balance * LAMPORTS_PER_SOL as f64) as u64;
+        let min = (self.config.min_balance * LAMPORTS_PER_SOL as f64) as u64;
+        let max = (self.config.max_balance * LAMPORTS_PER_SOL as f64) as u64;
+
+        if balance < min {
+            let amount = target.saturating_sub(balance);
+            info!(
+                "WSOL balance {} SOL below minimum, wrapping {} SOL",
+                balance as f64 / LAMPORTS_PER_SOL as f64,
+                amount as f64 / LAMPORTS_PER_SOL as f64
+            );
+            self.send(wrap_sol_instructions(&self.keypair.pubkey(), amount), &[])
+                .await?;
+        } else if max > 0 && balance > max {
+            let amount = balance.saturating_sub(target);
+            info!(
+                "WSOL balance {} SOL above maximum, unwrapping {} SOL",
+                balance as f64 / LAMPORTS_PER_SOL as f64,
+                amount as f64 / LAMPORTS_PER_SOL as f64
+            );
+            // Only the excess leaves, so the account snipes spend from is never closed.
+            let rent = self
+                .rpc_client
+                .get_minimum_balance_for_rent_exemption(TOKEN_ACCOUNT_LEN as usize)
+                .await?;
+            let temporary = Keypair::new();
+            let instructions =
+                unwrap_wsol_instructions(&self.keypair.pubkey(), &temporary.pubkey(), amount, rent);
+            self.send(instructions, &[&temporary]).await?;
+        }
+        Ok(())
+    }
+
+    /// Current WSOL balance in lamports; a missing account counts as empty.
+    async fn balance(&self) -> anyhow::Result<u64> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&self.wsol_account, CommitmentConfig::confirmed())
+            .await?
+            .value;
+        let Some(account) = account else {
+            return Ok(0);
+        };
+        let amount = account
+            .data
+            .get(TOKEN_ACCOUNT_AMOUNT_RANGE)
+            .context("WSOL account data too short")?;
+        Ok(u64::from_le_bytes(amount.try_into()?))
+    }
+
+    /// Signs with the wallet and `extra_signers`, then sends and confirms.
+    async fn send(
+        &self,
+        instructions: Vec<Instruction>,
+        extra_signers: &[&Keypair],
+    ) -> anyhow::Result<()> {
+        let blockhash = self.blockhash.get_or_fetch(&self.rpc_client).await?;
+        let message = Message::try_compile(&self.keypair.pubkey(), &instructions, &[], blockhash)?;
+        let mut signers = vec![self.keypair.as_ref()];
+        signers.extend_from_slice(extra_signers);
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &signers)?;
+        let signature = self.rpc_client.send_and_confirm_transaction(&tx).await?;
+        info!("WSOL maintenance tx confirmed: {signature}");
+        Ok(())
+    }
+}